@@ -0,0 +1,226 @@
+use crate::document::{classify, SectionKind};
+use crate::util::{sections, swapped, Fields};
+use crate::{Encoding, Las, ParseOptions, WellProp};
+
+/// Borrowed view of an entry in ~V, ~W, ~C or ~P section - every field is a slice of the
+/// line as written, trimmed
+///
+/// Fields are split as `Las` splits them, a description loses the curve number leading it,
+/// e.g. `DEPTH` for `1  DEPTH`. A repeated mnemonic is not renamed the way `Las` renames it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WellPropRef<'a> {
+    /// mnemonic, everything before the first dot
+    pub mnemonic: &'a str,
+    /// unit of measurement
+    pub unit: &'a str,
+    /// entry value
+    pub value: &'a str,
+    /// entry description
+    pub description: &'a str,
+    /// LAS 3.0 format of the entry, e.g. `F10.4` for `{F10.4}`
+    pub format: Option<&'a str>,
+    /// LAS 3.0 association of the entry, e.g. `Run[1]` for `| Run[1]`
+    pub association: Option<&'a str>,
+}
+
+impl<'a> WellPropRef<'a> {
+    // Returns the entry of a line, with value and description swapped if las12.
+    // `Las` reads its entries with it too
+    pub(crate) fn parse(line: &'a str, las12: bool) -> Self {
+        let line = line.trim();
+        let empty = &line[line.len()..];
+        let mut entry = Self {
+            mnemonic: line,
+            unit: empty,
+            value: empty,
+            description: empty,
+            format: None,
+            association: None,
+        };
+        match (line.find('.'), Fields::new(line)) {
+            (Some(dot), Some(fields)) => {
+                entry.mnemonic = line[..dot].trim();
+                entry.unit = &line[fields.unit];
+                entry.value = line[fields.value].trim();
+                entry.description = without_number(line[fields.description].trim());
+                entry.format = fields.format.map(|span| line[span].trim());
+                entry.association = fields.association.map(|span| line[span].trim());
+            }
+            // no colon, the description is empty
+            (Some(dot), None) => {
+                entry.mnemonic = line[..dot].trim();
+                let rest = &line[dot + 1..];
+                let unit = rest.split(char::is_whitespace).next().unwrap_or("");
+                entry.unit = unit;
+                entry.value = rest[unit.len()..].trim();
+            }
+            // no dot, the first word is the mnemonic
+            (None, _) => {
+                let mnemonic = line.split(char::is_whitespace).next().unwrap_or(line);
+                entry.mnemonic = mnemonic;
+                match line[mnemonic.len()..].rsplit_once(':') {
+                    Some((value, description)) => {
+                        entry.value = value.trim();
+                        entry.description = without_number(description.trim());
+                    }
+                    None => entry.value = line[mnemonic.len()..].trim(),
+                }
+            }
+        }
+        if swapped(entry.mnemonic, las12) {
+            std::mem::swap(&mut entry.value, &mut entry.description);
+        }
+        entry
+    }
+
+    /// Returns the entry as an owned `WellProp`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{LasRef, WellProp};
+    /// let log = LasRef::parse("~W\n STRT.M    1670.0 : START DEPTH\n");
+    /// assert_eq!(
+    ///     log.well[0].to_owned(),
+    ///     WellProp::new("M", "START DEPTH", "1670.0")
+    /// );
+    /// ```
+    pub fn to_owned(&self) -> WellProp {
+        let mut prop = WellProp::new(self.unit, self.description, self.value);
+        prop.format = self.format.map(str::to_owned);
+        prop.association = self.association.map(str::to_owned);
+        prop
+    }
+}
+
+/// Borrowed view of a well log file - the entries of the header sections as slices of the
+/// source, for reading the headers of many files without allocating a `String` per field
+///
+/// Only ~V, ~W, ~C, ~P, ~O and ~A sections (or their LAS 3.0 `~Log_*` counterparts) are read,
+/// the first of each kind. ~O and ~A sections are kept as written, ~A is not parsed
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::LasRef;
+/// let raw = std::fs::read_to_string("./sample/example.las").unwrap();
+/// let log = LasRef::parse(&raw);
+/// assert_eq!(log.headers()[..3], ["DEPT", "DT", "RHOB"]);
+/// let comp = log.well_prop("COMP").unwrap();
+/// assert_eq!(comp.value, "ANY OIL COMPANY INC.");
+/// let owned = log.to_owned();
+/// assert_eq!(owned.well_info().get("COMP"), Some(&comp.to_owned()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LasRef<'a> {
+    source: &'a str,
+    /// entries of ~V (version) section
+    pub version: Vec<WellPropRef<'a>>,
+    /// entries of ~W (well) section
+    pub well: Vec<WellPropRef<'a>>,
+    /// entries of ~C (curve) section
+    pub curves: Vec<WellPropRef<'a>>,
+    /// entries of ~P (parameter) section
+    pub parameters: Vec<WellPropRef<'a>>,
+    /// content of ~O (other) section as written, title line excluded
+    pub other: Option<&'a str>,
+    /// content of ~A (data) section as written, title line excluded
+    pub data: Option<&'a str>,
+}
+
+impl<'a> LasRef<'a> {
+    /// Returns a `LasRef` of the content of a well log file
+    ///
+    /// ## Arguments
+    ///
+    /// `source` - content of a well log file
+    pub fn parse(source: &'a str) -> Self {
+        let mut log = Self {
+            source,
+            ..Self::default()
+        };
+        let (mut curves, mut parameters) = (false, false);
+        for (_, content) in sections(source) {
+            let (name, kind, _) = classify(content);
+            if name != "Log" {
+                continue;
+            }
+            let body = content.split_once('\n').map_or("", |(_, body)| body);
+            match kind {
+                SectionKind::Version if log.version.is_empty() => {
+                    log.version = entries(body, false);
+                }
+                SectionKind::Well if log.well.is_empty() => {
+                    log.well = entries(body, log.las12());
+                }
+                SectionKind::Definition if !curves => {
+                    log.curves = entries(body, false);
+                    curves = true;
+                }
+                SectionKind::Parameter if !parameters => {
+                    log.parameters = entries(body, false);
+                    parameters = true;
+                }
+                SectionKind::Other if log.other.is_none() => log.other = Some(body),
+                SectionKind::Data if log.data.is_none() => log.data = Some(body),
+                _ => {}
+            }
+        }
+        log
+    }
+
+    /// Returns the content the view borrows from
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Returns the mnemonics of the curves in ~C (curve) section
+    pub fn headers(&self) -> Vec<&'a str> {
+        self.curves.iter().map(|curve| curve.mnemonic).collect()
+    }
+
+    /// Returns the first entry of ~W (well) section with the given mnemonic
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the entry, e.g. `UWI`
+    pub fn well_prop(&self, mnemonic: &str) -> Option<&WellPropRef<'a>> {
+        self.well.iter().find(|prop| prop.mnemonic == mnemonic)
+    }
+
+    /// Returns the owned `Las` of the source, parsed in full, ~A (data) section included
+    pub fn to_owned(&self) -> Las {
+        Las::from_blob(
+            self.source.to_owned(),
+            Encoding::Utf8,
+            &ParseOptions::default(),
+        )
+        .expect("parsing fails only in strict mode")
+    }
+
+    // Returns `true` for LAS 1.2 files, which swap values and descriptions in ~W (well) section
+    fn las12(&self) -> bool {
+        self.version
+            .iter()
+            .find(|prop| prop.mnemonic == "VERS")
+            .and_then(|vers| vers.value.parse::<f64>().ok())
+            .is_some_and(|vers| vers < 2.0)
+    }
+}
+
+// Returns the entries of the body of a section, comments and blank lines skipped
+fn entries(body: &str, las12: bool) -> Vec<WellPropRef<'_>> {
+    body.lines()
+        .filter(|line| !line.trim().is_empty() && !line.trim().starts_with('#'))
+        .map(|line| WellPropRef::parse(line, las12))
+        .collect()
+}
+
+// Returns a description without the curve number leading it, e.g. `DEPTH` for `1  DEPTH`
+fn without_number(description: &str) -> &str {
+    let rest = description.trim_start_matches(|c: char| c.is_ascii_digit());
+    match rest.strip_prefix(char::is_whitespace) {
+        Some(rest) if rest.len() + 1 < description.len() => rest.trim_start(),
+        _ => description,
+    }
+}
@@ -0,0 +1,32 @@
+/// Represents a curve of the `Log` data set - its entry in ~C (curve) section
+/// and its values in ~A (data) section, borrowed from the `Las` it belongs to
+///
+/// Values of string and datetime curves are missing values, see `Las::typed_column`
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::Las;
+/// let las = Las::new("./sample/example.las");
+/// let dt = las.curve("DT").unwrap();
+/// assert_eq!((dt.unit, dt.api_code), ("US/M", "60 520 32 00"));
+/// assert_eq!(dt.description, "SONIC TRANSIT TIME");
+/// assert_eq!(dt.position, 1);
+/// assert_eq!(dt.samples, [123.45, 123.45, 123.45, 123.45]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Curve<'a> {
+    /// mnemonic of the curve, repeated mnemonics are numbered from their second occurrence
+    /// e.g. `GR`, `GR:1`
+    pub mnemonic: &'a str,
+    /// unit of measurement
+    pub unit: &'a str,
+    /// API code, the value of the curve entry
+    pub api_code: &'a str,
+    /// curve description
+    pub description: &'a str,
+    /// position of the curve in ~C section and of its column in ~A section, starting from 0
+    pub position: usize,
+    /// values of the curve, one per row, empty if ~A section is absent
+    pub samples: &'a [f64],
+}
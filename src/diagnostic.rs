@@ -0,0 +1,166 @@
+use std::fmt;
+
+/// Represents how serious a `Diagnostic` or a validation `Issue` is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Severity {
+    /// the content was read, possibly not as intended
+    Warning,
+    /// the content could not be read as intended, e.g. a value is lost or misplaced
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Represents the kinds of problems reported by a `Diagnostic`
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    /// An entry has no dot between its mnemonic and unit,
+    /// its first word is read as the mnemonic
+    MissingDot,
+    /// An entry has no colon before its description, the description is read as empty
+    MissingColon,
+    /// A mnemonic appears more than once in a section,
+    /// the repeated entry is kept under a new mnemonic e.g. `GR:1` for the second `GR`
+    DuplicateMnemonic {
+        /// the mnemonic as written
+        mnemonic: String,
+        /// the mnemonic the entry is kept under
+        renamed: String,
+    },
+    /// A row in a data section does not hold one value per curve,
+    /// only the first 100 of a data section are reported
+    MalformedRow {
+        /// number of values expected, i.e. number of curves
+        expected: usize,
+        /// number of values found on the row
+        found: usize,
+    },
+    /// A section title is not one of the known sections and the section is skipped,
+    /// holds the title
+    UnknownSection(String),
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticKind::MissingDot => write!(f, "missing dot after the mnemonic"),
+            DiagnosticKind::MissingColon => write!(f, "missing colon before the description"),
+            DiagnosticKind::DuplicateMnemonic { mnemonic, renamed } => write!(
+                f,
+                "duplicate mnemonic `{}` renamed to `{}`",
+                mnemonic, renamed
+            ),
+            DiagnosticKind::MalformedRow { expected, found } => {
+                write!(f, "expected {} values, found {}", expected, found)
+            }
+            DiagnosticKind::UnknownSection(title) => write!(f, "unknown section `{}`", title),
+        }
+    }
+}
+
+/// Represents a problem found at a position of a well log file while parsing it
+///
+/// Renders as a report pointing at the offending text of the line
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::{DiagnosticKind, Las, Severity};
+/// let log: Las = "~V\n VERS. 2.0 : VERSION\n~W\n COMP.  ACME\n".parse().unwrap();
+/// let diagnostic = &log.diagnostics()[0];
+/// assert_eq!(diagnostic.severity, Severity::Warning);
+/// assert_eq!(diagnostic.kind, DiagnosticKind::MissingColon);
+/// assert_eq!((diagnostic.line, diagnostic.column), (4, 13));
+/// assert_eq!(
+///     diagnostic.to_string(),
+///     [
+///         "warning: missing colon before the description",
+///         " --> line 4, column 13",
+///         "  |",
+///         "4 |  COMP.  ACME",
+///         "  |             ^",
+///     ]
+///     .join("\n")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// how serious the problem is
+    pub severity: Severity,
+    /// what the problem is
+    pub kind: DiagnosticKind,
+    /// line number (starting from 1) of the offending text in the file
+    pub line: usize,
+    /// column (starting from 1) of the offending text on the line
+    pub column: usize,
+    /// length of the offending text, at least 1
+    pub width: usize,
+    /// the line as written
+    pub text: String,
+}
+
+impl Diagnostic {
+    // Returns a diagnostic pointing at span, a part of text, or at the end of text if empty
+    pub(crate) fn new(
+        severity: Severity,
+        kind: DiagnosticKind,
+        line: usize,
+        text: &str,
+        span: &str,
+    ) -> Self {
+        let start = span.as_ptr() as usize;
+        let column = match start.checked_sub(text.as_ptr() as usize) {
+            Some(offset) if offset <= text.len() && !span.is_empty() => offset + 1,
+            _ => text.len() + 1,
+        };
+        Self {
+            severity,
+            kind,
+            line,
+            column,
+            width: span.len().max(1),
+            text: text.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        // carets are aligned by characters, columns count bytes
+        let before = self
+            .text
+            .get(..self.column.saturating_sub(1))
+            .unwrap_or(&self.text);
+        let pointed = self
+            .text
+            .get(self.column.saturating_sub(1)..)
+            .and_then(|rest| rest.get(..self.width.min(rest.len())))
+            .map_or(1, |span| span.chars().count().max(1));
+        writeln!(f, "{}: {}", self.severity, self.kind)?;
+        writeln!(
+            f,
+            "{}--> line {}, column {}",
+            gutter, self.line, self.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(before.chars().count()),
+            "^".repeat(pointed)
+        )
+    }
+}
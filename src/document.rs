@@ -0,0 +1,807 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::util::{property, remove_comment, sections};
+use crate::{
+    CurveType, Diagnostic, DiagnosticKind, Encoding, HeaderSection, LasError, Severity, Warning,
+};
+
+/// Represents how missing values in data sections are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullPolicy {
+    /// keeps the `NULL` value of ~W (well) section as written,
+    /// values that are not numbers are stored as `NULL` too, or NaN if there is no `NULL` entry
+    #[default]
+    Keep,
+    /// stores `NULL` values and values that are not numbers as NaN
+    Nan,
+}
+
+/// Options used when parsing a well log file
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::{Las, NullPolicy, ParseOptions};
+/// let options = ParseOptions {
+///     null: NullPolicy::Nan,
+///     ..ParseOptions::default()
+/// };
+/// let log = Las::open_with("./sample/example.las", &options).unwrap();
+/// assert!(log.data()[3][3].is_nan());
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParseOptions {
+    /// returns `LasError::InvalidValue` for a data value that is not a number,
+    /// instead of storing it as a missing value
+    pub strict: bool,
+    /// how missing values are stored
+    pub null: NullPolicy,
+    /// types of curves by title, taking precedence over LAS 3.0 `{format}` declarations.
+    /// The type of any other curve is inferred from its values, in strict mode
+    /// a value that is not a number is only accepted in string and datetime curves
+    pub types: HashMap<String, CurveType>,
+    /// encoding of the file, detected from its content if `None`, see `Encoding::detect`.
+    /// Only applies when reading bytes, e.g. with `Las::open_with` or `Las::from_reader_with`
+    pub encoding: Option<Encoding>,
+    /// titles of the curves of ~C (curve) section to keep, every curve if `None`.
+    /// Other curves are dropped from ~C section and their values are not converted
+    pub curves: Option<Vec<String>>,
+    /// first and last index value, i.e. first value, of the rows of ~A (data) section to keep,
+    /// in either order and included, every row if `None`.
+    /// Values of other rows are not converted, nor are rows with a missing index value
+    pub index_range: Option<(f64, f64)>,
+}
+
+/// Represents the character separating values in data sections, set by the `DLM` entry
+/// of LAS 3.0 ~V (version) section
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Delimiter {
+    /// one or more spaces, the only delimiter in LAS 2.0
+    #[default]
+    Space,
+    /// a comma, optionally surrounded by spaces
+    Comma,
+    /// a tab character
+    Tab,
+}
+
+impl Delimiter {
+    // Returns the delimiter named by a `DLM` entry value
+    fn from_name(name: &str) -> Self {
+        match name.to_uppercase().as_str() {
+            "COMMA" => Delimiter::Comma,
+            "TAB" => Delimiter::Tab,
+            _ => Delimiter::Space,
+        }
+    }
+
+    // Splits a trimmed data line into its values
+    pub(crate) fn split<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        match self {
+            // runs of whitespace separate the values
+            Delimiter::Space => line.split_whitespace().collect(),
+            Delimiter::Comma => line.split(',').map(str::trim).collect(),
+            Delimiter::Tab => line.split('\t').map(str::trim).collect(),
+        }
+    }
+}
+
+/// Represents the entries of ~V (version) section
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VersionInfo {
+    /// unparsed value of the `VERS` entry
+    pub vers: String,
+    /// wrap mode, `true` if the `WRAP` entry is `YES`
+    pub wrap: bool,
+    /// delimiter of data values, `Delimiter::Space` unless set by a LAS 3.0 `DLM` entry
+    pub dlm: Delimiter,
+    /// every entry of the section, in file order
+    pub entries: HeaderSection,
+}
+
+impl VersionInfo {
+    /// Returns the `VERS` entry as `f64`, `None` if it is not a number
+    pub fn version(&self) -> Option<f64> {
+        self.vers.parse::<f64>().ok()
+    }
+}
+
+/// Represents the values of ~A (data) section, stored a column/curve at a time
+///
+/// Every column holds a value per row, values of string and datetime curves are stored as
+/// missing values. A row that does not hold one value per curve is padded with missing values,
+/// values beyond the last curve are dropped
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::Las;
+/// let las = Las::new("./sample/example.las");
+/// let data = las.data_set("Log").unwrap().data.as_ref().unwrap();
+/// assert_eq!(data.row_count(), 4);
+/// assert_eq!(data.column(0).unwrap()[1], 1669.875);
+/// assert_eq!(data.row(1).unwrap()[0], 1669.875);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DataSection {
+    // every Vec<f64> holds the values of a column/curve, one per row
+    pub(crate) columns: Vec<Vec<f64>>,
+    // number of rows, kept apart as a data section may have no column
+    pub(crate) row_count: usize,
+    /// type of every column/curve, declared or inferred from its values
+    pub types: Vec<CurveType>,
+    // values of string and datetime columns as written by column position, `None` if missing
+    pub(crate) text: HashMap<usize, Vec<Option<String>>>,
+    // first row that does not hold one value per curve - (line, expected, found)
+    pub(crate) malformed: Option<(usize, usize, usize)>,
+}
+
+/// Represents a data set - the definition, parameter and data sections of one kind of data
+///
+/// ~C, ~P and ~A sections form the `Log` data set, as do `~Log_Definition`, `~Log_Parameter`
+/// and `~Log_Data` in LAS 3.0. Other LAS 3.0 data sets such as `~Core_*` and `~Inclinometry_*`
+/// are named after their section prefix.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DataSet {
+    /// name of the data set, e.g. `Log`, `Core` or `Inclinometry`
+    pub name: String,
+    /// titles of the curves in the order they appear in the definition section
+    pub headers: Vec<String>,
+    /// `WellProp`(s) in the definition section, `None` if absent
+    pub definitions: Option<HeaderSection>,
+    /// `WellProp`(s) in the parameter section, `None` if absent
+    pub parameters: Option<HeaderSection>,
+    /// data section, `None` if absent
+    pub data: Option<DataSection>,
+}
+
+impl DataSection {
+    /// Returns the number of rows
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    /// Returns the values of every column/curve, in the order of the curves
+    pub fn columns(&self) -> &[Vec<f64>] {
+        &self.columns
+    }
+
+    /// Returns the values of the column/curve at a position, `None` if there is no such column
+    ///
+    /// ## Arguments
+    ///
+    /// `position` - position of the column, starting from 0
+    pub fn column(&self, position: usize) -> Option<&[f64]> {
+        self.columns.get(position).map(Vec::as_slice)
+    }
+
+    /// Returns the values of the column/curve at a position mutably,
+    /// `None` if there is no such column
+    ///
+    /// ## Arguments
+    ///
+    /// `position` - position of the column, starting from 0
+    pub fn column_mut(&mut self, position: usize) -> Option<&mut [f64]> {
+        self.columns.get_mut(position).map(Vec::as_mut_slice)
+    }
+
+    /// Returns the values of a row, a value per column/curve, `None` if there is no such row
+    ///
+    /// ## Arguments
+    ///
+    /// `index` - index of the row, starting from 0
+    pub fn row(&self, index: usize) -> Option<Vec<f64>> {
+        (index < self.row_count).then(|| self.columns.iter().map(|c| c[index]).collect())
+    }
+
+    /// Returns an iterator over the rows, every row gathered from the columns as it is yielded
+    pub fn rows(&self) -> impl Iterator<Item = Vec<f64>> + '_ {
+        (0..self.row_count).map(move |index| self.columns.iter().map(|c| c[index]).collect())
+    }
+}
+
+impl DataSet {
+    /// Returns the positions of the columns of a LAS 3.0 array curve, ordered by array index
+    ///
+    /// An array curve is defined once per element with an `[index]` suffix, e.g. `NMR[1]`, `NMR[2]`
+    ///
+    /// ## Arguments
+    ///
+    /// `name` - title of the array curve, without index
+    pub fn array(&self, name: &str) -> Vec<usize> {
+        let mut columns = self
+            .headers
+            .iter()
+            .enumerate()
+            .filter_map(|(position, title)| {
+                let (title, index) = title.strip_suffix(']')?.split_once('[')?;
+                let index = index.trim().parse::<usize>().ok()?;
+                Some((index, position)).filter(|_| title.trim() == name)
+            })
+            .collect::<Vec<_>>();
+        columns.sort_unstable();
+        columns.into_iter().map(|(_, position)| position).collect()
+    }
+}
+
+/// Owned model of a well log file, built in a single pass over its content
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::LasDocument;
+/// let raw = std::fs::read_to_string("./sample/example.las").unwrap();
+/// let doc = LasDocument::parse(&raw);
+/// assert_eq!(doc.version.as_ref().unwrap().version(), Some(2.0));
+/// let log = doc.log().unwrap();
+/// assert_eq!(log.headers.len(), 8);
+/// assert_eq!(log.data.as_ref().unwrap().row_count(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LasDocument {
+    /// ~V (version) section, `None` if absent
+    pub version: Option<VersionInfo>,
+    /// `WellProp`(s) in ~W (well) section, `None` if absent
+    pub well: Option<HeaderSection>,
+    /// text in ~O (other) section, `None` if absent
+    pub other: Option<String>,
+    /// data sets in the order they first appear, the `Log` data set included
+    pub data_sets: Vec<DataSet>,
+    /// problems found while parsing that did not prevent reading the file
+    pub warnings: Vec<Warning>,
+    /// problems found while parsing with their position in the file, in file order
+    /// for header sections followed by those of data sections
+    pub diagnostics: Vec<Diagnostic>,
+    // line number of the title, data set name and kind of every section, in file order
+    pub(crate) sections: Vec<(usize, String, SectionKind)>,
+}
+
+// Kind of content held by a section
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SectionKind {
+    Version,
+    Well,
+    Definition,
+    Parameter,
+    Data,
+    Other,
+    Unknown,
+}
+
+impl LasDocument {
+    /// Returns a `LasDocument` parsed from the content of a well log file
+    ///
+    /// Only the first occurrence of every section is used,
+    /// sections missing from the content are left as `None`
+    ///
+    /// ## Arguments
+    ///
+    /// `raw_str` - content of the well log file
+    pub fn parse(raw_str: &str) -> Self {
+        // parsing only fails in strict mode
+        Self::parse_with(raw_str, &ParseOptions::default()).unwrap_or_default()
+    }
+
+    /// Returns a `LasDocument` parsed from the content of a well log file with the given options,
+    /// or `LasError::InvalidValue` for a data value that is not a number in strict mode
+    ///
+    /// ## Arguments
+    ///
+    /// `raw_str` - content of the well log file
+    ///
+    /// `options` - parse options
+    pub fn parse_with(raw_str: &str, options: &ParseOptions) -> Result<Self, LasError> {
+        let mut doc = Self::default();
+        // data sections are parsed once all the definitions are known
+        let mut data = vec![];
+        for (line, content) in sections(raw_str) {
+            let (name, kind, association) = classify(content);
+            doc.sections.push((line, name.clone(), kind));
+            match kind {
+                SectionKind::Version if doc.version.is_none() => {
+                    let entries = property(line, content, false, &mut doc.diagnostics);
+                    let value = |mnemonic| entries.get(mnemonic).map(|e| e.value.clone());
+                    doc.version = Some(VersionInfo {
+                        vers: value("VERS").unwrap_or_default(),
+                        wrap: value("WRAP").is_some_and(|wrap| wrap.eq_ignore_ascii_case("yes")),
+                        dlm: value("DLM")
+                            .map(|dlm| Delimiter::from_name(&dlm))
+                            .unwrap_or_default(),
+                        entries,
+                    });
+                }
+                SectionKind::Well if doc.well.is_none() => {
+                    let las12 = doc.las12();
+                    doc.well = Some(property(line, content, las12, &mut doc.diagnostics))
+                }
+                SectionKind::Other if doc.other.is_none() => {
+                    doc.other = Some(
+                        remove_comment(content)
+                            .into_iter()
+                            .skip(1)
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    )
+                }
+                SectionKind::Definition => {
+                    let mut diagnostics = vec![];
+                    let set = doc.data_set_mut(&name);
+                    if set.definitions.is_none() {
+                        let definitions = property(line, content, false, &mut diagnostics);
+                        set.headers = definitions.keys().cloned().collect();
+                        set.definitions = Some(definitions);
+                    }
+                    doc.diagnostics.append(&mut diagnostics);
+                }
+                SectionKind::Parameter => {
+                    let mut diagnostics = vec![];
+                    let set = doc.data_set_mut(&name);
+                    if set.parameters.is_none() {
+                        set.parameters = Some(property(line, content, false, &mut diagnostics));
+                    }
+                    doc.diagnostics.append(&mut diagnostics);
+                }
+                SectionKind::Unknown => {
+                    let title = content.lines().next().unwrap_or("");
+                    let kind = DiagnosticKind::UnknownSection(title.trim().to_owned());
+                    doc.diagnostics.push(Diagnostic::new(
+                        Severity::Warning,
+                        kind,
+                        line,
+                        title,
+                        title.trim(),
+                    ));
+                }
+                SectionKind::Data => {
+                    doc.data_set_mut(&name);
+                    if data.iter().all(|(n, _, _, _)| n != &name) {
+                        data.push((name, line, content, association));
+                    }
+                }
+                _ => {}
+            }
+        }
+        let (wrap, dlm) = doc
+            .version
+            .as_ref()
+            .map(|v| (v.wrap, v.dlm))
+            .unwrap_or_default();
+        let format = DataFormat {
+            wrap,
+            dlm,
+            null: doc.null_value(),
+        };
+        let mut diagnostics = vec![];
+        for (name, line, content, association) in data {
+            let definitions = association
+                .and_then(|a| doc.data_set(&a).filter(|set| set.definitions.is_some()))
+                .map(|set| (set.headers.clone(), set.definitions.clone()));
+            let set = doc.data_set_mut(&name);
+            if let (true, Some((headers, definitions))) = (set.definitions.is_none(), definitions) {
+                set.headers = headers;
+                set.definitions = definitions;
+            }
+            let section = parse_data(line, content, set, &format, options, &mut diagnostics)?;
+            set.data = Some(section);
+        }
+        doc.diagnostics.append(&mut diagnostics);
+        if let Some(keep) = doc.log().and_then(|set| selected(set, options)) {
+            select(doc.data_set_mut("Log"), &keep);
+        }
+        doc.warnings = doc
+            .diagnostics
+            .iter()
+            .filter_map(|diagnostic| match &diagnostic.kind {
+                DiagnosticKind::DuplicateMnemonic { mnemonic, renamed } => {
+                    Some(Warning::DuplicateMnemonic {
+                        line: diagnostic.line,
+                        mnemonic: mnemonic.clone(),
+                        renamed: renamed.clone(),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(doc)
+    }
+
+    /// Returns the `NULL` entry of ~W (well) section as `f64`, `None` if absent or not a number
+    pub fn null_value(&self) -> Option<f64> {
+        self.well
+            .as_ref()?
+            .get("NULL")
+            .and_then(|null| null.value.parse::<f64>().ok())
+    }
+
+    // Returns `true` for LAS 1.2 files, which swap values and descriptions in ~W (well) section
+    pub(crate) fn las12(&self) -> bool {
+        self.version
+            .as_ref()
+            .and_then(|v| v.version())
+            .is_some_and(|v| v < 2.0)
+    }
+
+    /// Returns the `Log` data set - ~C, ~P and ~A sections, `None` if none of them is present
+    pub fn log(&self) -> Option<&DataSet> {
+        self.data_set("Log")
+    }
+
+    /// Returns the data set with the given name, ignoring case
+    ///
+    /// ## Arguments
+    ///
+    /// `name` - name of the data set, e.g. `Log`, `Core` or `Inclinometry`
+    pub fn data_set(&self, name: &str) -> Option<&DataSet> {
+        self.data_sets
+            .iter()
+            .find(|set| set.name.eq_ignore_ascii_case(name))
+    }
+
+    // Returns the data set with the given name, added if missing
+    pub(crate) fn data_set_mut(&mut self, name: &str) -> &mut DataSet {
+        let position = self
+            .data_sets
+            .iter()
+            .position(|set| set.name.eq_ignore_ascii_case(name));
+        let position = position.unwrap_or_else(|| {
+            self.data_sets.push(DataSet {
+                name: name.to_owned(),
+                ..DataSet::default()
+            });
+            self.data_sets.len() - 1
+        });
+        &mut self.data_sets[position]
+    }
+}
+
+// Returns the data set name, kind and associated data set name of a section from its title,
+// LAS 3.0 titles are `~<Name>_<Kind> | <Association>`, e.g. `~Core_Data | Core_Definition`
+pub(crate) fn classify(content: &str) -> (String, SectionKind, Option<String>) {
+    let title = content.lines().next().unwrap_or("").trim();
+    let title = title.strip_prefix('~').unwrap_or(title);
+    let (title, association) = match title.split_once('|') {
+        Some((title, association)) => (title, Some(association.trim())),
+        None => (title, None),
+    };
+    let association = association.map(|name| match name.rsplit_once('_') {
+        Some((prefix, _)) => prefix.to_owned(),
+        None => name.to_owned(),
+    });
+    let word = title.split_whitespace().next().unwrap_or("");
+    // other suffixes are LAS 2.0 titles, e.g. `~Curve_Information`
+    if let Some((name, kind)) = word.rsplit_once('_') {
+        let kind = match kind.to_lowercase().as_str() {
+            "definition" => Some(SectionKind::Definition),
+            "parameter" => Some(SectionKind::Parameter),
+            "data" => Some(SectionKind::Data),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            return (name.to_owned(), kind, association);
+        }
+    }
+    let kind = match word.get(..1).map(|x| x.to_uppercase()).as_deref() {
+        Some("V") => SectionKind::Version,
+        Some("W") => SectionKind::Well,
+        Some("C") => SectionKind::Definition,
+        Some("P") => SectionKind::Parameter,
+        Some("O") => SectionKind::Other,
+        Some("A") => SectionKind::Data,
+        _ => SectionKind::Unknown,
+    };
+    ("Log".to_owned(), kind, association)
+}
+
+// Layout of the values in data sections
+struct DataFormat {
+    wrap: bool,
+    dlm: Delimiter,
+    null: Option<f64>,
+}
+
+// A value in a data section and its position in the file
+struct Token<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+// Splits a ~A (data) section into rows of values, first_line - line number of the section title,
+// calls on_row with every row, the line it starts on and the text of that line as written.
+// Without wrap every line is a row. With wrap lines are joined until the row holds
+// a value per curve, a last row may be short.
+fn for_each_row<'a, F>(
+    first_line: usize,
+    raw_str: &'a str,
+    expected: usize,
+    format: &DataFormat,
+    mut on_row: F,
+) -> Result<(), LasError>
+where
+    F: FnMut(usize, &'a str, Vec<Token<'a>>) -> Result<(), LasError>,
+{
+    let mut row: Vec<Token> = vec![];
+    // line number and text of the line where the row starts
+    let mut row_line = first_line;
+    let mut row_text = "";
+    for (i, raw_line) in raw_str.lines().enumerate().skip(1) {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if row.is_empty() {
+            row_line = first_line + i;
+            row_text = raw_line;
+        }
+        row.extend(format.dlm.split(line).into_iter().map(|text| Token {
+            text,
+            line: first_line + i,
+            column: text.as_ptr() as usize - raw_line.as_ptr() as usize + 1,
+        }));
+        if row_complete(format.wrap, row.len(), expected) {
+            on_row(row_line, row_text, std::mem::take(&mut row))?;
+        }
+    }
+    if !row.is_empty() {
+        on_row(row_line, row_text, row)?;
+    }
+    Ok(())
+}
+
+// Returns the type of every curve of set declared in options or by LAS 3.0 `{format}`,
+// `None` if undeclared
+pub(crate) fn declared_types(set: &DataSet, options: &ParseOptions) -> Vec<Option<CurveType>> {
+    set.headers
+        .iter()
+        .map(|title| {
+            options.types.get(title).copied().or_else(|| {
+                let prop = set.definitions.as_ref()?.get(title)?;
+                CurveType::from_format(prop.format.as_deref()?)
+            })
+        })
+        .collect()
+}
+
+// Returns the positions of the curves of the `Log` data set kept by options, in file order,
+// `None` to keep every curve
+pub(crate) fn selected(set: &DataSet, options: &ParseOptions) -> Option<Vec<usize>> {
+    let curves = options.curves.as_ref().filter(|_| set.name == "Log")?;
+    let keep = set
+        .headers
+        .iter()
+        .enumerate()
+        .filter(|(_, title)| curves.contains(title))
+        .map(|(column, _)| column)
+        .collect();
+    Some(keep)
+}
+
+// Keeps only the curves at the given positions in the definitions of set
+pub(crate) fn select(set: &mut DataSet, keep: &[usize]) {
+    let headers = keep
+        .iter()
+        .filter_map(|&column| set.headers.get(column).cloned())
+        .collect::<Vec<_>>();
+    set.definitions = set.definitions.take().map(|definitions| {
+        definitions
+            .into_iter()
+            .filter(|(mnemonic, _)| headers.contains(mnemonic))
+            .collect()
+    });
+    set.headers = headers;
+}
+
+// Returns `true` if a row with the given index value, i.e. first value, is within the index
+// range of options, always `true` without a range
+pub(crate) fn in_window(options: &ParseOptions, index: Option<&str>, null: Option<f64>) -> bool {
+    let Some((start, end)) = options.index_range else {
+        return true;
+    };
+    index
+        .and_then(|index| index.parse::<f64>().ok())
+        .filter(|&index| Some(index) != null)
+        .is_some_and(|index| start.min(end) <= index && index <= start.max(end))
+}
+
+// Returns `true` if a row of row_len values is complete - every line is a row without wrap,
+// in wrap mode a row spans as many lines as it takes to hold the expected values
+pub(crate) fn row_complete(wrap: bool, row_len: usize, expected: usize) -> bool {
+    !wrap || row_len >= expected
+}
+
+// Returns the value missing values are stored as
+pub(crate) fn missing_value(options: &ParseOptions, null: Option<f64>) -> f64 {
+    match (options.null, null) {
+        (NullPolicy::Keep, Some(null)) => null,
+        _ => f64::NAN,
+    }
+}
+
+// Number of rows tokenized before their values are converted, bounds the memory held by tokens
+const BATCH: usize = 1 << 14;
+
+// Number of rows converted by a single task
+const RUN: usize = 1 << 9;
+
+// Number of malformed rows of a data section reported as diagnostics, the others are not
+const MALFORMED_ROWS: usize = 100;
+
+// Kinds of values found in a column, `NULL` values aside
+#[derive(Clone, Default)]
+struct ColumnValues {
+    numbers: bool,
+    fractions: bool,
+    text: bool,
+}
+
+// Parses the rows of a ~A (data) section into the data section of set
+fn parse_data(
+    first_line: usize,
+    raw_str: &str,
+    set: &DataSet,
+    format: &DataFormat,
+    options: &ParseOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<DataSection, LasError> {
+    let expected = set.headers.len();
+    let missing = missing_value(options, format.null);
+    let declared = declared_types(set, options);
+    let is_text = |column: usize| {
+        matches!(
+            declared.get(column),
+            Some(Some(CurveType::String)) | Some(Some(CurveType::DateTime))
+        )
+    };
+    // positions in the file of the columns kept, all of them if `None`
+    let selected = selected(set, options);
+    let kept = selected.as_ref().map_or(expected, Vec::len);
+    let file_column = |position: usize| selected.as_ref().map_or(position, |keep| keep[position]);
+    let wanted = |tokens: &[Token]| in_window(options, tokens.first().map(|t| t.text), format.null);
+    // converts the values of a run of rows into columns, noting the kinds of values found in
+    // every column. Values missing from a row are stored as missing values
+    let convert = |rows: &[Vec<Token>]| {
+        let mut columns = vec![ColumnValues::default(); kept];
+        let mut converted = (0..kept)
+            .map(|_| Vec::with_capacity(rows.len()))
+            .collect::<Vec<_>>();
+        let mut count = 0;
+        for tokens in rows.iter().filter(|tokens| wanted(tokens)) {
+            for (position, (values, found)) in converted.iter_mut().zip(&mut columns).enumerate() {
+                let column = file_column(position);
+                let Some(token) = tokens.get(column) else {
+                    values.push(missing);
+                    continue;
+                };
+                values.push(match token.text.parse::<f64>() {
+                    Ok(value) if Some(value) == format.null => missing,
+                    Ok(value) => {
+                        found.numbers = true;
+                        found.fractions = found.fractions || token.text.parse::<i64>().is_err();
+                        value
+                    }
+                    Err(_) if options.strict && !is_text(column) => {
+                        return Err(LasError::InvalidValue {
+                            line: token.line,
+                            column: token.column,
+                            value: token.text.to_owned(),
+                        })
+                    }
+                    Err(_) => {
+                        found.text = true;
+                        missing
+                    }
+                });
+            }
+            count += 1;
+        }
+        Ok((converted, count, columns))
+    };
+    let mut columns = vec![ColumnValues::default(); kept];
+    let mut values: Vec<Vec<f64>> = vec![vec![]; kept];
+    let mut row_count = 0;
+    // rows are converted a batch at a time, in runs spread over all cores with `rayon`
+    let mut flush = |batch: &mut Vec<Vec<Token>>| -> Result<(), LasError> {
+        #[cfg(feature = "rayon")]
+        let converted = batch.par_chunks(RUN).map(convert).collect::<Vec<_>>();
+        #[cfg(not(feature = "rayon"))]
+        let converted = batch.chunks(RUN).map(convert).collect::<Vec<_>>();
+        // the first error in file order wins
+        for run in converted {
+            let (converted, count, found) = run?;
+            row_count += count;
+            for (column, converted) in values.iter_mut().zip(converted) {
+                column.extend(converted);
+            }
+            for (values, found) in columns.iter_mut().zip(found) {
+                values.numbers |= found.numbers;
+                values.fractions |= found.fractions;
+                values.text |= found.text;
+            }
+        }
+        batch.clear();
+        Ok(())
+    };
+    let mut batch = Vec::with_capacity(BATCH);
+    let mut malformed = None;
+    let mut reported = 0;
+    for_each_row(
+        first_line,
+        raw_str,
+        expected,
+        format,
+        |line, text, tokens| {
+            if tokens.len() != expected && reported < MALFORMED_ROWS {
+                malformed = malformed.or(Some((line, expected, tokens.len())));
+                reported += 1;
+                let kind = DiagnosticKind::MalformedRow {
+                    expected,
+                    found: tokens.len(),
+                };
+                diagnostics.push(Diagnostic::new(
+                    Severity::Error,
+                    kind,
+                    line,
+                    text,
+                    text.trim(),
+                ));
+            }
+            batch.push(tokens);
+            if batch.len() == BATCH {
+                flush(&mut batch)?;
+            }
+            Ok(())
+        },
+    )?;
+    flush(&mut batch)?;
+    let mut types = (0..kept)
+        .map(
+            |position| match (declared[file_column(position)], &columns[position]) {
+                (Some(kind), _) => kind,
+                (None, values) if values.text => CurveType::String,
+                (None, values) if values.numbers && !values.fractions => CurveType::Integer,
+                _ => CurveType::Float,
+            },
+        )
+        .collect::<Vec<_>>();
+    // values of string and datetime curves are read again as written
+    let mut text: HashMap<usize, Vec<Option<String>>> = types
+        .iter()
+        .enumerate()
+        .filter(|(_, kind)| matches!(kind, CurveType::String | CurveType::DateTime))
+        .map(|(position, _)| (position, Vec::with_capacity(row_count)))
+        .collect();
+    if !text.is_empty() {
+        for_each_row(first_line, raw_str, expected, format, |_, _, tokens| {
+            if !wanted(&tokens) {
+                return Ok(());
+            }
+            for (position, values) in text.iter_mut() {
+                values.push(
+                    tokens
+                        .get(file_column(*position))
+                        .filter(|token| token.text.parse::<f64>().ok() != format.null)
+                        .map(|token| token.text.to_owned()),
+                );
+            }
+            Ok(())
+        })?;
+        // string curves whose values are all dates or times are datetime curves
+        for (position, values) in text.iter() {
+            if declared[file_column(*position)].is_none()
+                && values.iter().flatten().all(|x| CurveType::is_datetime(x))
+            {
+                types[*position] = CurveType::DateTime;
+            }
+        }
+    }
+    Ok(DataSection {
+        columns: values,
+        row_count,
+        types,
+        text,
+        malformed,
+    })
+}
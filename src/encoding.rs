@@ -0,0 +1,171 @@
+use std::{borrow::Cow, convert::TryFrom, io};
+
+use crate::LasError;
+
+// Characters of Windows-1252 bytes 0x80 to 0x9F, bytes left undefined by Windows-1252
+// are read as the control codes of the same value so that they are written back as read
+const WINDOWS_1252: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+// UTF-8 byte order mark
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Represents the character encoding of a well log file
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::Encoding;
+/// let bytes = b"COMP.   COMPA\xD1\xCDA PETROLERA : COMPANY";
+/// assert_eq!(Encoding::detect(bytes), Encoding::Latin1);
+/// let text = Encoding::Latin1.decode(bytes).unwrap();
+/// assert!(text.contains("COMPAÑÍA"));
+/// assert_eq!(Encoding::Latin1.encode(&text).unwrap(), bytes);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// UTF-8
+    #[default]
+    Utf8,
+    /// UTF-8 starting with a byte order mark (`EF BB BF`), which is removed when decoding
+    /// and written back when encoding
+    Utf8Bom,
+    /// ISO-8859-1, one byte per character from `U+0000` to `U+00FF`
+    Latin1,
+    /// Windows-1252, Latin-1 with characters such as `€`, `‰` and `“` in place of
+    /// the control codes from 0x80 to 0x9F
+    Windows1252,
+}
+
+impl Encoding {
+    /// Returns the encoding of the content of a well log file - UTF-8 if the content is valid
+    /// UTF-8, with a byte order mark if it starts with one, otherwise Windows-1252 if it holds
+    /// bytes from 0x80 to 0x9F, Latin-1 if not
+    ///
+    /// ## Arguments
+    ///
+    /// `bytes` - content of a well log file
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Encoding;
+    /// let bytes = b"\xEF\xBB\xBF~VERSION INFORMATION";
+    /// assert_eq!(Encoding::detect(bytes), Encoding::Utf8Bom);
+    /// assert_eq!(Encoding::Utf8Bom.decode(bytes).unwrap(), "~VERSION INFORMATION");
+    /// ```
+    pub fn detect(bytes: &[u8]) -> Self {
+        if std::str::from_utf8(bytes).is_ok() {
+            match bytes.starts_with(BOM) {
+                true => Encoding::Utf8Bom,
+                false => Encoding::Utf8,
+            }
+        } else if bytes.iter().any(|b| (0x80..0xA0).contains(b)) {
+            Encoding::Windows1252
+        } else {
+            Encoding::Latin1
+        }
+    }
+
+    /// Returns the content of a well log file as text, without byte order mark in `Utf8Bom`,
+    /// or `LasError::Io` if the encoding is UTF-8 and the content is not valid UTF-8
+    ///
+    /// Every byte is a character in Latin-1 and Windows-1252, decoding never fails
+    /// and `encode` gives back the bytes as read
+    ///
+    /// ## Arguments
+    ///
+    /// `bytes` - content of a well log file
+    pub fn decode(self, bytes: &[u8]) -> Result<String, LasError> {
+        self.decode_borrowed(bytes).map(Cow::into_owned)
+    }
+
+    // Returns bytes decoded as text, borrowed from bytes in UTF-8
+    pub(crate) fn decode_borrowed(self, bytes: &[u8]) -> Result<Cow<'_, str>, LasError> {
+        match self {
+            Encoding::Utf8 => std::str::from_utf8(bytes)
+                .map(Cow::Borrowed)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err).into()),
+            Encoding::Utf8Bom => {
+                Encoding::Utf8.decode_borrowed(bytes.strip_prefix(BOM).unwrap_or(bytes))
+            }
+            Encoding::Latin1 | Encoding::Windows1252 => {
+                let mut text = String::with_capacity(bytes.len());
+                self.decode_into(bytes, &mut text)?;
+                Ok(Cow::Owned(text))
+            }
+        }
+    }
+
+    // Returns bytes decoded as text, the buffer of bytes in UTF-8 is moved rather than copied
+    pub(crate) fn decode_owned(self, mut bytes: Vec<u8>) -> Result<String, LasError> {
+        match self {
+            Encoding::Utf8 => String::from_utf8(bytes)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err).into()),
+            Encoding::Utf8Bom => {
+                if bytes.starts_with(BOM) {
+                    bytes.drain(..BOM.len());
+                }
+                Encoding::Utf8.decode_owned(bytes)
+            }
+            Encoding::Latin1 | Encoding::Windows1252 => self.decode(&bytes),
+        }
+    }
+
+    // Appends bytes decoded as text to out, which keeps its allocation from call to call
+    pub(crate) fn decode_into(self, bytes: &[u8], out: &mut String) -> Result<(), LasError> {
+        match self {
+            Encoding::Utf8 | Encoding::Utf8Bom => {
+                let bytes = match self {
+                    Encoding::Utf8Bom => bytes.strip_prefix(BOM).unwrap_or(bytes),
+                    _ => bytes,
+                };
+                let text = std::str::from_utf8(bytes)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                out.push_str(text);
+            }
+            Encoding::Latin1 => out.extend(bytes.iter().map(|&b| char::from(b))),
+            Encoding::Windows1252 => out.extend(bytes.iter().map(|&b| match b {
+                0x80..=0x9F => WINDOWS_1252[usize::from(b - 0x80)],
+                _ => char::from(b),
+            })),
+        }
+        Ok(())
+    }
+
+    /// Returns text as bytes in this encoding, preceded by a byte order mark in `Utf8Bom`,
+    /// or `LasError::Unencodable` for a character the encoding cannot represent
+    ///
+    /// ## Arguments
+    ///
+    /// `text` - content of a well log file
+    pub fn encode(self, text: &str) -> Result<Vec<u8>, LasError> {
+        match self {
+            Encoding::Utf8 => return Ok(text.as_bytes().to_vec()),
+            Encoding::Utf8Bom => {
+                let text = text.strip_prefix('\u{FEFF}').unwrap_or(text);
+                return Ok([BOM, text.as_bytes()].concat());
+            }
+            _ => {}
+        }
+        let byte = |c: char| match WINDOWS_1252.iter().position(|&w| w == c) {
+            Some(position) if self == Encoding::Windows1252 => Some(0x80 + position as u8),
+            // control codes 0x80 to 0x9F only stand for themselves in Latin-1
+            _ => u8::try_from(u32::from(c))
+                .ok()
+                .filter(|b| self == Encoding::Latin1 || !(0x80..0xA0).contains(b)),
+        };
+        text.chars()
+            .map(|c| {
+                byte(c).ok_or(LasError::Unencodable {
+                    character: c,
+                    encoding: self,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,154 @@
+use std::{error::Error, fmt, io};
+
+use crate::Encoding;
+
+/// Represents the ways loading or reading a well log file can fail
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LasError {
+    /// The file or reader could not be read
+    Io(io::Error),
+    /// A section required by the operation is absent, holds the section signature e.g. `~C`
+    MissingSection(String),
+    /// The `VERS` entry in ~V (version) section is missing or not a number, holds the raw value
+    InvalidVersion(String),
+    /// No curve with the given mnemonic exists in ~C (curve) section
+    UnknownCurve(String),
+    /// A mnemonic is empty or holds characters that separate the fields of an entry,
+    /// i.e. spaces, `.`, `:`, `{`, `}` or `|`, or starts with `~` or `#`. Holds the mnemonic.
+    /// The occurrence number of a repeated mnemonic, e.g. `GR:1`, is allowed
+    InvalidMnemonic(String),
+    /// A curve with the given mnemonic already exists in ~C (curve) section
+    DuplicateCurve(String),
+    /// The values of a new curve are not one per row of ~A (data) section
+    LengthMismatch {
+        /// number of values expected, i.e. number of rows
+        expected: usize,
+        /// number of values given
+        found: usize,
+    },
+    /// A character cannot be written in the encoding of the file
+    Unencodable {
+        /// the character
+        character: char,
+        /// encoding the file is written in
+        encoding: Encoding,
+    },
+    /// A rule file of `validate::HouseRules` cannot be read, holds the reason
+    InvalidRules(String),
+    /// A row in ~A (data) section does not hold one value per curve
+    MalformedRow {
+        /// line number (starting from 1) of the row in the file
+        line: usize,
+        /// number of values expected, i.e. number of curves
+        expected: usize,
+        /// number of values found on the row
+        found: usize,
+    },
+    /// A value in ~A (data) section is not a number, returned in strict mode only
+    InvalidValue {
+        /// line number (starting from 1) of the value in the file
+        line: usize,
+        /// column (starting from 1) of the value on the line
+        column: usize,
+        /// the value as written
+        value: String,
+    },
+    /// The `Las` holds part of the file and cannot be written - it was read by
+    /// `Las::open_headers`, or parsed with `ParseOptions::curves` or `ParseOptions::index_range`
+    /// and written losslessly
+    PartialDocument,
+}
+
+impl fmt::Display for LasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LasError::Io(err) => write!(f, "unable to read las file: {}", err),
+            LasError::MissingSection(section) => write!(f, "missing {} section", section),
+            LasError::InvalidVersion(raw) => write!(f, "invalid version `{}`", raw),
+            LasError::UnknownCurve(curve) => write!(f, "unknown curve `{}`", curve),
+            LasError::InvalidMnemonic(mnemonic) => write!(f, "invalid mnemonic `{}`", mnemonic),
+            LasError::DuplicateCurve(curve) => write!(f, "curve `{}` already exists", curve),
+            LasError::LengthMismatch { expected, found } => {
+                write!(
+                    f,
+                    "expected {} values, one per row, found {}",
+                    expected, found
+                )
+            }
+            LasError::Unencodable {
+                character,
+                encoding,
+            } => write!(f, "`{}` cannot be written in {:?}", character, encoding),
+            LasError::InvalidRules(reason) => write!(f, "invalid rules: {}", reason),
+            LasError::MalformedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} values, found {}",
+                line, expected, found
+            ),
+            LasError::InvalidValue {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "line {}, column {}: `{}` is not a number",
+                line, column, value
+            ),
+            LasError::PartialDocument => {
+                write!(f, "a partially read file cannot be written")
+            }
+        }
+    }
+}
+
+impl Error for LasError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LasError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LasError {
+    fn from(err: io::Error) -> Self {
+        LasError::Io(err)
+    }
+}
+
+/// Represents a problem found while parsing a well log file that did not prevent reading it
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// A mnemonic appears more than once in a section,
+    /// the repeated entry is kept under a new mnemonic e.g. `GR:1` for the second `GR`
+    DuplicateMnemonic {
+        /// line number (starting from 1) of the repeated entry in the file
+        line: usize,
+        /// the mnemonic as written
+        mnemonic: String,
+        /// the mnemonic the entry is kept under
+        renamed: String,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::DuplicateMnemonic {
+                line,
+                mnemonic,
+                renamed,
+            } => write!(
+                f,
+                "line {}: duplicate mnemonic `{}` renamed to `{}`",
+                line, mnemonic, renamed
+            ),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use std::{iter::FromIterator, slice, vec};
+
+use crate::WellProp;
+
+/// Represents an entry of a section along with its mnemonic
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderItem {
+    /// mnemonic of the entry, e.g. `STRT` or `DEPT`
+    pub mnemonic: String,
+    /// unit, value and description of the entry
+    pub prop: WellProp,
+}
+
+/// Represents the `WellProp`(s) of a section in the order they appear in the file,
+/// looked up by mnemonic
+///
+/// The entries of ~C (curve) section are in the order of the columns in ~A (data) section
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::Las;
+/// let log = Las::new("./sample/example.las");
+/// let curves = log.curve_params();
+/// assert_eq!(curves.keys().next().map(String::as_str), Some("DEPT"));
+/// assert_eq!(curves.position("RHOB"), Some(2));
+/// assert_eq!(curves.get("RHOB").unwrap().unit, "K/M3");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HeaderSection {
+    items: Vec<HeaderItem>,
+}
+
+impl HeaderSection {
+    /// Returns an empty `HeaderSection`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the `WellProp` with the given mnemonic, `None` if absent
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the entry, e.g. `STRT`
+    pub fn get(&self, mnemonic: &str) -> Option<&WellProp> {
+        self.items
+            .iter()
+            .find(|item| item.mnemonic == mnemonic)
+            .map(|item| &item.prop)
+    }
+
+    /// Returns the `WellProp` with the given mnemonic mutably, `None` if absent
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the entry, e.g. `STRT`
+    pub fn get_mut(&mut self, mnemonic: &str) -> Option<&mut WellProp> {
+        self.items
+            .iter_mut()
+            .find(|item| item.mnemonic == mnemonic)
+            .map(|item| &mut item.prop)
+    }
+
+    /// Returns `true` if an entry has the given mnemonic
+    pub fn contains_key(&self, mnemonic: &str) -> bool {
+        self.position(mnemonic).is_some()
+    }
+
+    /// Returns the position of the entry with the given mnemonic in the section,
+    /// `None` if absent
+    pub fn position(&self, mnemonic: &str) -> Option<usize> {
+        self.items.iter().position(|item| item.mnemonic == mnemonic)
+    }
+
+    /// Sets the `WellProp` of a mnemonic, in place if the mnemonic exists, appended otherwise.
+    /// Returns the replaced `WellProp`, `None` if the mnemonic is new
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the entry
+    ///
+    /// `prop` - unit, value and description of the entry
+    pub fn insert(&mut self, mnemonic: String, prop: WellProp) -> Option<WellProp> {
+        match self.get_mut(&mnemonic) {
+            Some(old) => Some(std::mem::replace(old, prop)),
+            None => {
+                self.items.push(HeaderItem { mnemonic, prop });
+                None
+            }
+        }
+    }
+
+    /// Removes the entry with the given mnemonic keeping the order of the others,
+    /// returns its `WellProp`, `None` if absent
+    pub fn remove(&mut self, mnemonic: &str) -> Option<WellProp> {
+        let position = self.position(mnemonic)?;
+        Some(self.items.remove(position).prop)
+    }
+
+    /// Changes the mnemonic of an entry keeping its position,
+    /// returns `false` if the mnemonic is absent or the new one already taken
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - current mnemonic of the entry
+    ///
+    /// `new` - mnemonic the entry is kept under
+    pub fn rename(&mut self, mnemonic: &str, new: String) -> bool {
+        if self.contains_key(&new) {
+            return false;
+        }
+        match self.items.iter_mut().find(|item| item.mnemonic == mnemonic) {
+            Some(item) => {
+                item.mnemonic = new;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of entries
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the section has no entry
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the entries in order
+    pub fn items(&self) -> &[HeaderItem] {
+        &self.items
+    }
+
+    /// Returns an iterator over the mnemonics and `WellProp`(s), in order
+    pub fn iter(&self) -> HeaderIter<'_> {
+        HeaderIter(self.items.iter())
+    }
+
+    /// Returns an iterator over the mnemonics, in order
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.items.iter().map(|item| &item.mnemonic)
+    }
+
+    /// Returns an iterator over the `WellProp`(s), in order
+    pub fn values(&self) -> impl Iterator<Item = &WellProp> {
+        self.items.iter().map(|item| &item.prop)
+    }
+}
+
+/// Iterator over the mnemonics and `WellProp`(s) of a `HeaderSection`
+pub struct HeaderIter<'a>(slice::Iter<'a, HeaderItem>);
+
+impl<'a> Iterator for HeaderIter<'a> {
+    type Item = (&'a String, &'a WellProp);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|item| (&item.mnemonic, &item.prop))
+    }
+}
+
+/// Owning iterator over the mnemonics and `WellProp`(s) of a `HeaderSection`
+pub struct HeaderIntoIter(vec::IntoIter<HeaderItem>);
+
+impl Iterator for HeaderIntoIter {
+    type Item = (String, WellProp);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|item| (item.mnemonic, item.prop))
+    }
+}
+
+impl IntoIterator for HeaderSection {
+    type Item = (String, WellProp);
+    type IntoIter = HeaderIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        HeaderIntoIter(self.items.into_iter())
+    }
+}
+
+impl<'a> IntoIterator for &'a HeaderSection {
+    type Item = (&'a String, &'a WellProp);
+    type IntoIter = HeaderIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<(String, WellProp)> for HeaderSection {
+    fn from_iter<T: IntoIterator<Item = (String, WellProp)>>(iter: T) -> Self {
+        let mut section = Self::new();
+        for (mnemonic, prop) in iter {
+            section.insert(mnemonic, prop);
+        }
+        section
+    }
+}
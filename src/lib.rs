@@ -3,26 +3,70 @@
 //! lasrs is a crate used to parse geophysical well log files `.las`.
 //! Provides utilities for extracting strongly typed information from the files.
 //! Supports Las Version 2.0 by [Canadian Well Logging Society](http://www.cwls.org) -
-//! [Specification](https://www.cwls.org/wp-content/uploads/2017/02/Las2_Update_Feb2017.pdf)
+//! [Specification](https://www.cwls.org/wp-content/uploads/2017/02/Las2_Update_Feb2017.pdf),
+//! and reading of Las Version 3.0 data sets -
+//! [Specification](https://www.cwls.org/wp-content/uploads/2014/09/LAS_3_File_Structure.pdf)
 
 #[macro_use]
 extern crate lazy_static;
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Write};
-use std::{collections::HashMap, path::Path};
+use std::sync::OnceLock;
+use std::{path::Path, str::FromStr};
 
+use document::{classify, SectionKind};
+
+mod borrowed;
+mod curve;
+mod diagnostic;
+mod document;
+mod encoding;
+mod error;
+mod header;
+mod reader;
+mod syntax;
 mod util;
-use util::{metadata, property, remove_comment, SPACES, SPACES_AND_DOT};
+pub mod validate;
+mod value;
+mod well;
+mod writer;
 
+pub use borrowed::{LasRef, WellPropRef};
+pub use curve::Curve;
+pub use diagnostic::{Diagnostic, DiagnosticKind, Severity};
+pub use document::{
+    DataSection, DataSet, Delimiter, LasDocument, NullPolicy, ParseOptions, VersionInfo,
+};
+pub use encoding::Encoding;
+pub use error::{LasError, Warning};
+pub use header::{HeaderIntoIter, HeaderItem, HeaderIter, HeaderSection};
+pub use reader::LasReader;
+pub use syntax::{LineKind, SyntaxLine, SyntaxSection, SyntaxTree};
 pub use util::WellProp;
+pub use value::{CurveType, CurveValue};
+pub use well::{Measure, WellHeader};
+pub use writer::WriteOptions;
 
 /// Represents a parsed well log file
 pub struct Las {
-    /// blob holds the String data read from the file
+    /// blob holds the String data read from the file, transcoded to UTF-8
     /// ## Note
-    /// There's no need to access the blob field, only exposed for debugging
+    /// There's no need to access the blob field, only exposed for debugging.
+    /// The accessors read from the `LasDocument` parsed on construction,
+    /// changes to blob are not reflected in them. Lossless writing starts from blob
     pub blob: String,
+    document: LasDocument,
+    // options the document was parsed with
+    options: ParseOptions,
+    // encoding the file was read with
+    encoding: Encoding,
+    // positions of the curves of the `Log` data set by mnemonic,
+    // built on first use and cleared whenever the document may change
+    curve_positions: OnceLock<HashMap<String, usize>>,
+    // `true` if read by `open_headers`, without data
+    headers_only: bool,
 }
 
 impl Las {
@@ -40,11 +84,389 @@ impl Las {
     /// assert_eq!(&log.blob[..=7], "~VERSION");
     /// ```
     pub fn new<T: AsRef<Path>>(path: T) -> Self {
-        let mut blob = String::new();
-        let f = File::open(path.as_ref()).expect("Invalid path, verify existence of file");
-        let mut br = BufReader::new(f);
-        br.read_to_string(&mut blob).expect("Unable to read file");
-        Self { blob }
+        Self::open(path).expect("Invalid path, verify existence of file")
+    }
+
+    /// Returns a `Las` read from a las file with the given path,
+    /// or `LasError::Io` if the file cannot be read
+    ///
+    /// ## Arguments
+    ///
+    /// `path` - Path to well log file
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{Las, LasError};
+    /// let log = Las::open("./sample/example.las").unwrap();
+    /// assert_eq!(&log.blob[..=7], "~VERSION");
+    /// assert!(matches!(Las::open("./sample/missing.las"), Err(LasError::Io(_))));
+    /// ```
+    pub fn open<T: AsRef<Path>>(path: T) -> Result<Self, LasError> {
+        Self::open_with(path, &ParseOptions::default())
+    }
+
+    /// Returns a `Las` read from a las file with the given path and parse options,
+    /// or an error if the file cannot be read or, in strict mode, holds a value that is not a number
+    ///
+    /// ## Arguments
+    ///
+    /// `path` - Path to well log file
+    ///
+    /// `options` - parse options
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{Las, ParseOptions};
+    /// let options = ParseOptions {
+    ///     strict: true,
+    ///     ..ParseOptions::default()
+    /// };
+    /// let log = Las::open_with("./sample/example.las", &options).unwrap();
+    /// assert_eq!(log.row_count(), 4);
+    /// ```
+    pub fn open_with<T: AsRef<Path>>(path: T, options: &ParseOptions) -> Result<Self, LasError> {
+        let f = File::open(path.as_ref())?;
+        Self::from_reader_with(BufReader::new(f), options)
+    }
+
+    /// Returns a `Las` of the header sections of a las file with the given path,
+    /// reading stops at the title of ~A (data) section.
+    /// The `Las` holds no data, see `data_offset` and `LasReader::resume` to read it later,
+    /// and `write_to` returns `LasError::PartialDocument`
+    ///
+    /// ## Arguments
+    ///
+    /// `path` - Path to well log file
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::open_headers("./sample/example.las").unwrap();
+    /// assert_eq!(log.headers().len(), 8);
+    /// assert_eq!(log.well_info().get("COMP").unwrap().value, "ANY OIL COMPANY INC.");
+    /// assert_eq!(log.row_count(), 0);
+    /// assert_eq!(log.data_offset(), Some(2630));
+    /// ```
+    pub fn open_headers<T: AsRef<Path>>(path: T) -> Result<Self, LasError> {
+        Self::open_headers_with(path, &ParseOptions::default())
+    }
+
+    /// Returns a `Las` of the header sections of a las file with the given path and
+    /// parse options, reading stops at the title of ~A (data) section
+    ///
+    /// ## Arguments
+    ///
+    /// `path` - Path to well log file
+    ///
+    /// `options` - parse options
+    pub fn open_headers_with<T: AsRef<Path>>(
+        path: T,
+        options: &ParseOptions,
+    ) -> Result<Self, LasError> {
+        let mut reader = BufReader::new(File::open(path.as_ref())?);
+        let header = reader::read_header(&mut reader)?;
+        let mut las = Self::from_vec_with(header.bytes, options)?;
+        las.headers_only = true;
+        Ok(las)
+    }
+
+    /// Returns a `Las` read from any reader, e.g. stdin or a network stream,
+    /// or `LasError::Io` if reading fails. The encoding is detected from the content,
+    /// see `Encoding::detect`
+    ///
+    /// ## Arguments
+    ///
+    /// `reader` - source of the well log content
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let f = std::fs::File::open("./sample/example.las").unwrap();
+    /// let log = Las::from_reader(f).unwrap();
+    /// assert_eq!(log.version(), 2.0);
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, LasError> {
+        Self::from_reader_with(reader, &ParseOptions::default())
+    }
+
+    /// Returns a `Las` read from any reader with the given parse options,
+    /// or an error if reading fails or, in strict mode, a value is not a number
+    ///
+    /// ## Arguments
+    ///
+    /// `reader` - source of the well log content
+    ///
+    /// `options` - parse options
+    pub fn from_reader_with<R: Read>(
+        mut reader: R,
+        options: &ParseOptions,
+    ) -> Result<Self, LasError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        Self::from_vec_with(bytes, options)
+    }
+
+    /// Returns a `Las` read from a byte slice in any supported encoding,
+    /// see `Encoding::detect`
+    ///
+    /// ## Arguments
+    ///
+    /// `bytes` - content of a well log file
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let bytes = std::fs::read("./sample/example.las").unwrap();
+    /// let log = Las::from_bytes(&bytes).unwrap();
+    /// assert_eq!(log.column_count(), 8);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LasError> {
+        Self::from_bytes_with(bytes, &ParseOptions::default())
+    }
+
+    // Decodes the content of a well log file in the encoding of options, or the detected one.
+    // UTF-8 content is parsed from bytes and copied once into the blob
+    fn from_bytes_with(bytes: &[u8], options: &ParseOptions) -> Result<Self, LasError> {
+        let encoding = options.encoding.unwrap_or_else(|| Encoding::detect(bytes));
+        let text = encoding.decode_borrowed(bytes)?;
+        Ok(Self {
+            document: LasDocument::parse_with(&text, options)?,
+            blob: text.into_owned(),
+            options: options.clone(),
+            encoding,
+            curve_positions: OnceLock::new(),
+            headers_only: false,
+        })
+    }
+
+    // Decodes the content of a well log file like `from_bytes_with`, UTF-8 content keeps
+    // the buffer of bytes as blob
+    fn from_vec_with(bytes: Vec<u8>, options: &ParseOptions) -> Result<Self, LasError> {
+        let encoding = options.encoding.unwrap_or_else(|| Encoding::detect(&bytes));
+        Self::from_blob(encoding.decode_owned(bytes)?, encoding, options)
+    }
+
+    // Parses the content of a well log file, every constructor ends up here
+    pub(crate) fn from_blob(
+        blob: String,
+        encoding: Encoding,
+        options: &ParseOptions,
+    ) -> Result<Self, LasError> {
+        Ok(Self {
+            document: LasDocument::parse_with(&blob, options)?,
+            blob,
+            options: options.clone(),
+            encoding,
+            curve_positions: OnceLock::new(),
+            headers_only: false,
+        })
+    }
+
+    /// Returns the encoding the file was read with, detected or set in `ParseOptions`,
+    /// UTF-8 for a `Las` parsed from a `&str`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{Encoding, Las};
+    /// let log = Las::new("./sample/example.las");
+    /// assert_eq!(log.encoding(), Encoding::Utf8);
+    /// ```
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Returns the byte offset in the file of the line following the title of ~A (data) section,
+    /// `None` if there is no ~A section
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// let offset = log.data_offset().unwrap() as usize;
+    /// let bytes = std::fs::read("./sample/example.las").unwrap();
+    /// assert!(bytes[offset..].starts_with(b"1670.000"));
+    /// ```
+    pub fn data_offset(&self) -> Option<u64> {
+        self.data_start().map(|(.., offset)| offset)
+    }
+
+    // Returns the line number of the title of ~A (data) section, the index in blob where the
+    // title starts and the byte offset in the file of the line following it
+    fn data_start(&self) -> Option<(usize, usize, u64)> {
+        // a byte order mark takes 3 bytes, in the file only if decoded as `Utf8Bom`
+        let body = util::without_bom(&self.blob);
+        let mut index = self.blob.len() - body.len();
+        let mut offset = match self.encoding {
+            Encoding::Utf8Bom => 3,
+            _ => index,
+        };
+        for (i, line) in body.split_inclusive('\n').enumerate() {
+            // every character is a byte in encodings other than UTF-8
+            offset += match self.encoding {
+                Encoding::Utf8 | Encoding::Utf8Bom => line.len(),
+                _ => line.chars().count(),
+            };
+            let title = line.trim_start().starts_with('~');
+            if title && matches!(classify(line), (name, SectionKind::Data, _) if name == "Log") {
+                return Some((i + 1, index, offset as u64));
+            }
+            index += line.len();
+        }
+        None
+    }
+
+    /// Returns the `LasDocument` holding every parsed section of the file
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// assert_eq!(log.document().log().unwrap().headers, log.headers());
+    /// ```
+    pub fn document(&self) -> &LasDocument {
+        &self.document
+    }
+
+    /// Returns the `LasDocument` holding every parsed section of the file mutably,
+    /// changes are written by `write_to` and `save`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let mut log = Las::new("./sample/example.las");
+    /// let well = log.document_mut().well.as_mut().unwrap();
+    /// well.get_mut("COMP").unwrap().value = "ACME".to_owned();
+    /// assert_eq!(log.well_info().get("COMP").unwrap().value, "ACME");
+    /// ```
+    pub fn document_mut(&mut self) -> &mut LasDocument {
+        self.curve_positions.take();
+        &mut self.document
+    }
+
+    /// Returns the data sets of the file, ~C, ~P and ~A sections form the `Log` data set,
+    /// LAS 3.0 files may hold others such as `Core` or `Inclinometry`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// assert_eq!(log.data_sets().len(), 1);
+    /// assert_eq!(log.data_sets()[0].name, "Log");
+    /// ```
+    pub fn data_sets(&self) -> &[DataSet] {
+        &self.document.data_sets
+    }
+
+    /// Returns the data set with the given name ignoring case, `None` if absent
+    ///
+    /// ## Arguments
+    ///
+    /// `name` - name of the data set, e.g. `Log`, `Core` or `Inclinometry`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/las3.las");
+    /// let core = log.data_set("core").unwrap();
+    /// assert_eq!(core.headers, vec!["CORE_TOP", "CORE_BASE", "CORE_RECOVERY"]);
+    /// ```
+    pub fn data_set(&self, name: &str) -> Option<&DataSet> {
+        self.document.data_set(name)
+    }
+
+    /// Returns the problems found while parsing the file that did not prevent reading it
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{Las, Warning};
+    /// let log = Las::new("./sample/duplicates.las");
+    /// assert_eq!(log.warnings().len(), 3);
+    /// assert_eq!(
+    ///     log.warnings()[1],
+    ///     Warning::DuplicateMnemonic {
+    ///         line: 17,
+    ///         mnemonic: "GR".to_owned(),
+    ///         renamed: "GR:1".to_owned(),
+    ///     }
+    /// );
+    /// ```
+    pub fn warnings(&self) -> &[Warning] {
+        &self.document.warnings
+    }
+
+    /// Returns the `Report` of checking the file against the LAS 2.0 specification,
+    /// see `validate::check`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/A10.las");
+    /// assert!(log.validate().is_conforming());
+    /// ```
+    pub fn validate(&self) -> validate::Report {
+        validate::check(self)
+    }
+
+    /// Returns the `Report` of checking the file against the LAS 2.0 specification
+    /// and a company's house rules, the issues of the specification first
+    ///
+    /// ## Arguments
+    ///
+    /// `rules` - house-standard requirements on the entries of ~W, ~C and ~P sections
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::validate::{HeaderRule, HouseRules, RuleSection};
+    /// use lasrs::{Las, Severity};
+    /// let rules = HouseRules {
+    ///     rules: vec![HeaderRule {
+    ///         severity: Severity::Warning,
+    ///         ..HeaderRule::new(RuleSection::Parameter, "RMF")
+    ///     }],
+    /// };
+    /// let report = Las::new("./sample/A10.las").validate_with(&rules);
+    /// assert!(report.is_conforming());
+    /// assert_eq!(report.warnings().count(), 1);
+    /// ```
+    pub fn validate_with(&self, rules: &validate::HouseRules) -> validate::Report {
+        let mut report = validate::check(self);
+        report.issues.extend(rules.check(self).issues);
+        report
+    }
+
+    /// Returns the problems found while parsing the file, with their line, column and the
+    /// offending text, e.g. an entry missing its colon or a row with the wrong number of values.
+    /// Every `Diagnostic` renders as a report pointing at the offending text
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{DiagnosticKind, Las, Severity};
+    /// let log = Las::new("./sample/malformed.las");
+    /// let row = log
+    ///     .diagnostics()
+    ///     .iter()
+    ///     .find(|d| d.severity == Severity::Error)
+    ///     .unwrap();
+    /// assert!(matches!(row.kind, DiagnosticKind::MalformedRow { .. }));
+    /// println!("{}", row);
+    /// ```
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.document.diagnostics
     }
 
     /// Returns `f64` representing the version of Las specification
@@ -57,9 +479,27 @@ impl Las {
     /// assert_eq!(log.version(), 2.0);
     /// ```
     pub fn version(&self) -> f64 {
-        metadata(&self.blob)
-            .and_then(|(v, _)| v)
-            .expect("Invalid version")
+        self.try_version().expect("Invalid version")
+    }
+
+    /// Returns `f64` representing the version of Las specification,
+    /// or an error if ~V (version) section or its `VERS` entry is invalid
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// assert_eq!(log.try_version().unwrap(), 2.0);
+    /// ```
+    pub fn try_version(&self) -> Result<f64, LasError> {
+        let info = self
+            .document
+            .version
+            .as_ref()
+            .ok_or_else(|| LasError::MissingSection("~V".to_owned()))?;
+        info.version()
+            .ok_or_else(|| LasError::InvalidVersion(info.vers.clone()))
     }
 
     /// Returns a `bool` denoting the wrap mode
@@ -72,7 +512,11 @@ impl Las {
     /// assert_eq!(log.wrap(), false);
     /// ```
     pub fn wrap(&self) -> bool {
-        metadata(&self.blob).map(|(_, w)| w).unwrap_or_default()
+        self.document
+            .version
+            .as_ref()
+            .map(|v| v.wrap)
+            .unwrap_or_default()
     }
 
     /// Returns `Vec<String>` representing the titles of the curves (~C),
@@ -89,23 +533,23 @@ impl Las {
     /// );
     /// ```
     pub fn headers(&self) -> Vec<String> {
-        self.blob
-            .splitn(2, "~C")
-            .nth(1)
-            .unwrap_or("")
-            .splitn(2, "~")
-            .nth(0)
-            .map(|x| remove_comment(x))
-            .unwrap_or_default()
-            .into_iter()
-            .skip(1)
-            .filter_map(|x| {
-                SPACES_AND_DOT
-                    .splitn(x.trim(), 2)
-                    .next()
-                    .map(|x| x.to_string())
-            })
-            .collect()
+        self.titles().to_vec()
+    }
+
+    /// Returns `Vec<String>` representing the titles of the curves (~C),
+    /// or `LasError::MissingSection` if there is no ~C (curve) section
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// assert_eq!(log.try_headers().unwrap().len(), 8);
+    /// ```
+    pub fn try_headers(&self) -> Result<Vec<String>, LasError> {
+        self.curve_section()
+            .map(|_| self.titles().to_vec())
+            .ok_or_else(|| LasError::MissingSection("~C".to_owned()))
     }
 
     /// Returns `Vec<Vec<f64>>` where every Vec<f64> represents a row in ~A (data) section,
@@ -123,28 +567,88 @@ impl Las {
     /// assert_eq!(expected, &log.data()[3..5]);
     /// ```
     pub fn data(&self) -> Vec<Vec<f64>> {
-        self.blob
-            .splitn(2, "~A")
-            .nth(1)
-            .unwrap_or("")
-            .lines()
-            .skip(1)
-            .flat_map(|x| {
-                SPACES
-                    .split(x.trim())
-                    .map(|v| v.trim().parse::<f64>().unwrap_or(0.0))
-            })
-            .collect::<Vec<f64>>()
-            .chunks(self.headers().len())
-            .map(|ch| Vec::from(ch))
-            .collect()
+        self.values()
+            .map(|d| d.rows().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns `Vec<Vec<f64>>` where every Vec<f64> represents a row in ~A (data) section,
+    /// or an error if ~A (data) section is missing or a row does not hold one value per curve
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// assert_eq!(log.try_data().unwrap().len(), 4);
+    /// ```
+    pub fn try_data(&self) -> Result<Vec<Vec<f64>>, LasError> {
+        Ok(self.try_values()?.rows().collect())
     }
 
-    /// Returns `Vec<f64>` - all reading for a curve/column
+    // Returns ~A (data) section, validated as in `try_data`
+    fn try_values(&self) -> Result<&DataSection, LasError> {
+        let data = self
+            .log()
+            .and_then(|l| l.data.as_ref())
+            .ok_or_else(|| LasError::MissingSection("~A".to_owned()))?;
+        if self.curve_section().is_none() {
+            return Err(LasError::MissingSection("~C".to_owned()));
+        }
+        match data.malformed {
+            Some((line, expected, found)) => Err(LasError::MalformedRow {
+                line,
+                expected,
+                found,
+            }),
+            None => Ok(data),
+        }
+    }
+
+    /// Returns the `NULL` entry of ~W (well) section as `f64`, `None` if absent or not a number
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// assert_eq!(log.null_value(), Some(-999.25));
+    /// ```
+    pub fn null_value(&self) -> Option<f64> {
+        self.document.null_value()
+    }
+
+    /// Returns `Vec<Vec<Option<f64>>>` where every Vec represents a row in ~A (data) section,
+    /// and every `None` a missing value - the `NULL` value of ~W (well) section
+    /// or a value that is not a number
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// let data = log.nullable_data();
+    /// assert_eq!(data[3][0], Some(1669.745));
+    /// assert_eq!(data[3][3], None);
+    /// ```
+    pub fn nullable_data(&self) -> Vec<Vec<Option<f64>>> {
+        let null = self.null_value();
+        let rows = self.values().map(DataSection::rows).into_iter().flatten();
+        rows.map(|row| {
+            row.into_iter()
+                .map(|v| Some(v).filter(|v| !v.is_nan() && Some(*v) != null))
+                .collect()
+        })
+        .collect()
+    }
+
+    /// Returns `&[f64]` - all reading for a curve/column, borrowed from ~A (data) section,
+    /// `None` if no curve has the given title or ~A section is absent
     ///
     /// ## Arguments
     ///
-    /// `col` - string slice representing the title of the column
+    /// `col` - string slice representing the title of the column,
+    /// repeated titles are numbered from their second occurrence e.g. `GR`, `GR:1`, `GR:2`
     ///
     /// ## Example
     ///
@@ -152,17 +656,119 @@ impl Las {
     /// use lasrs::Las;
     /// let log = Las::new("./sample/example.las");
     /// assert_eq!(
-    ///     vec![1670.0, 1669.875, 1669.75, 1669.745],
+    ///     Some(&[1670.0, 1669.875, 1669.75, 1669.745][..]),
     ///     log.column("DEPT")
     /// );
+    /// assert_eq!(log.column("GR"), None);
+    /// ```
+    pub fn column(&self, col: &str) -> Option<&[f64]> {
+        let index = self.titles().iter().position(|x| x == col)?;
+        self.values()?.column(index)
+    }
+
+    /// Returns `Vec<f64>` - all reading for a curve/column,
+    /// or `LasError::UnknownCurve` if no curve has the given title
+    ///
+    /// ## Arguments
+    ///
+    /// `col` - string slice representing the title of the column
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{Las, LasError};
+    /// let log = Las::new("./sample/example.las");
+    /// assert_eq!(log.try_column("DEPT").unwrap().len(), 4);
+    /// assert!(matches!(log.try_column("GR"), Err(LasError::UnknownCurve(_))));
+    /// ```
+    pub fn try_column(&self, col: &str) -> Result<Vec<f64>, LasError> {
+        let index = self
+            .try_headers()?
+            .into_iter()
+            .position(|x| x == col)
+            .ok_or_else(|| LasError::UnknownCurve(col.to_owned()))?;
+        let column = self.try_values()?.column(index).unwrap_or_default();
+        Ok(column.to_vec())
+    }
+
+    /// Returns `Vec<CurveType>` - the type of every column/curve, in the order of `headers`
+    ///
+    /// Types are declared by LAS 3.0 `{format}` or `ParseOptions::types`,
+    /// or inferred from the values of the curve
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{CurveType, Las};
+    /// let log = Las::new("./sample/typed.las");
+    /// assert_eq!(
+    ///     log.curve_types(),
+    ///     vec![
+    ///         CurveType::Float,
+    ///         CurveType::Float,
+    ///         CurveType::String,
+    ///         CurveType::Integer,
+    ///         CurveType::DateTime,
+    ///         CurveType::DateTime,
+    ///     ]
+    /// );
+    /// ```
+    pub fn curve_types(&self) -> Vec<CurveType> {
+        self.log()
+            .and_then(|l| l.data.as_ref())
+            .map(|d| d.types.clone())
+            .unwrap_or_else(|| vec![CurveType::Float; self.column_count()])
+    }
+
+    /// Returns all readings for a curve/column as values of its type, `None` for a missing value,
+    /// or an error if no curve has the given title or ~A (data) section is invalid
+    ///
+    /// ## Arguments
+    ///
+    /// `col` - string slice representing the title of the column
+    ///
+    /// ## Example
+    ///
     /// ```
-    pub fn column(self, col: &str) -> Vec<f64> {
+    /// use lasrs::{CurveValue, Las};
+    /// let log = Las::new("./sample/typed.las");
+    /// let lith = log.typed_column("LITH").unwrap();
+    /// assert_eq!(lith[0], Some(CurveValue::String("SAND".to_owned())));
+    /// assert_eq!(lith[2], None);
+    /// ```
+    pub fn typed_column(&self, col: &str) -> Result<Vec<Option<CurveValue>>, LasError> {
         let index = self
-            .headers()
+            .try_headers()?
             .into_iter()
-            .position(|x| x == col.to_owned())
-            .expect("msg");
-        self.data().into_iter().map(|x| x[index]).collect()
+            .position(|x| x == col)
+            .ok_or_else(|| LasError::UnknownCurve(col.to_owned()))?;
+        let data = self.try_values()?;
+        let kind = data.types.get(index).copied().unwrap_or(CurveType::Float);
+        if let Some(text) = data.text.get(&index) {
+            return Ok(text
+                .iter()
+                .map(|value| {
+                    value.clone().map(|value| match kind {
+                        CurveType::DateTime => CurveValue::DateTime(value),
+                        _ => CurveValue::String(value),
+                    })
+                })
+                .collect());
+        }
+        let null = self.null_value();
+        Ok(data
+            .column(index)
+            .unwrap_or_default()
+            .iter()
+            .map(|&v| {
+                Some(v)
+                    .filter(|v| !v.is_nan() && Some(*v) != null)
+                    .map(|v| match kind {
+                        CurveType::Integer => CurveValue::Integer(v as i64),
+                        _ => CurveValue::Float(v),
+                    })
+            })
+            .collect())
     }
 
     /// Returns `usize` representing the total number of columns/curves
@@ -175,7 +781,7 @@ impl Las {
     /// assert_eq!(8, log.column_count());
     /// ```
     pub fn column_count(&self) -> usize {
-        self.headers().len()
+        self.titles().len()
     }
 
     /// Returns `usize` representing the total number of entry in ~A (data) section
@@ -188,11 +794,136 @@ impl Las {
     /// assert_eq!(4, log.row_count());
     /// ```
     pub fn row_count(&self) -> usize {
-        self.data().len()
+        self.values().map_or(0, DataSection::row_count)
+    }
+
+    // Returns ~A (data) section, `None` if absent
+    fn values(&self) -> Option<&DataSection> {
+        self.log().and_then(|l| l.data.as_ref())
+    }
+
+    // Returns the `Log` data set - ~C, ~P and ~A sections
+    fn log(&self) -> Option<&DataSet> {
+        self.document.log()
+    }
+
+    // Returns the `Log` data set mutably
+    fn log_mut(&mut self) -> Option<&mut DataSet> {
+        self.curve_positions.take();
+        self.document
+            .data_sets
+            .iter_mut()
+            .find(|set| set.name.eq_ignore_ascii_case("Log"))
+    }
+
+    // Returns the `Log` data set if it has a curve with the given mnemonic
+    fn curve_set_mut(&mut self, mnemonic: &str) -> Result<&mut DataSet, LasError> {
+        self.log_mut()
+            .filter(|log| log.headers.iter().any(|title| title == mnemonic))
+            .ok_or_else(|| LasError::UnknownCurve(mnemonic.to_owned()))
+    }
+
+    // Returns the `WellProp`(s) in ~C (curve) section
+    fn curve_section(&self) -> Option<&HeaderSection> {
+        self.log().and_then(|l| l.definitions.as_ref())
+    }
+
+    // Returns the titles of the curves in ~C (curve) section, empty if the section is absent
+    fn titles(&self) -> &[String] {
+        self.log().map(|l| l.headers.as_slice()).unwrap_or_default()
+    }
+
+    /// Returns `Vec<Curve>` - every curve of ~C (curve) section with its values
+    /// in ~A (data) section, in the order of ~C section
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// let curves = log.curves();
+    /// assert_eq!(curves.len(), 8);
+    /// assert_eq!(curves[2].mnemonic, "RHOB");
+    /// assert_eq!(curves[2].samples[0], 2550.0);
+    /// ```
+    pub fn curves(&self) -> Vec<Curve<'_>> {
+        (0..self.titles().len())
+            .map(|position| self.curve_at(position))
+            .collect()
+    }
+
+    /// Returns the curve with the given mnemonic, `None` if there is no such curve
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the curve, e.g. `GR`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// let ild = log.curve("ILD").unwrap();
+    /// assert_eq!((ild.position, ild.unit), (7, "OHMM"));
+    /// assert_eq!(ild.samples[0], 105.6);
+    /// assert!(log.curve("GR").is_none());
+    /// ```
+    pub fn curve(&self, mnemonic: &str) -> Option<Curve<'_>> {
+        let positions = self.curve_positions.get_or_init(|| {
+            let mut positions = HashMap::with_capacity(self.titles().len());
+            // the first of repeated titles is found, as in ~C section
+            for (position, title) in self.titles().iter().enumerate() {
+                positions.entry(title.clone()).or_insert(position);
+            }
+            positions
+        });
+        positions
+            .get(mnemonic)
+            .map(|&position| self.curve_at(position))
+    }
+
+    // Returns the curve at a position of ~C (curve) section, its samples borrowed from
+    // ~A (data) section
+    fn curve_at(&self, position: usize) -> Curve<'_> {
+        let title = &self.titles()[position];
+        // definitions are in the order of the titles, looked up otherwise
+        let prop = self
+            .curve_section()
+            .and_then(|d| match d.items().get(position) {
+                Some(item) if &item.mnemonic == title => Some(&item.prop),
+                _ => d.get(title),
+            });
+        Curve {
+            mnemonic: title,
+            unit: prop.map_or("", |p| p.unit.as_str()),
+            api_code: prop.map_or("", |p| p.value.as_str()),
+            description: prop.map_or("", |p| p.description.as_str()),
+            position,
+            samples: self
+                .values()
+                .and_then(|d| d.column(position))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the index curve - the first curve of ~C (curve) section, which LAS 2.0 requires
+    /// to be a depth, time or index curve, `None` if there is no curve
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// let index = log.index_curve().unwrap();
+    /// assert_eq!((index.mnemonic, index.unit), ("DEPT", "M"));
+    /// assert_eq!(index.samples.last(), Some(&1669.745));
+    /// ```
+    pub fn index_curve(&self) -> Option<Curve<'_>> {
+        (!self.titles().is_empty()).then(|| self.curve_at(0))
     }
 
     /// Returns `Vec<(String, String)>` where the first item in the tuple is the title of curve
-    /// and the second is the full description of the curve
+    /// and the second is the full description of the curve, in the order of ~C (curve) section
     ///
     /// ## Example
     ///
@@ -209,14 +940,13 @@ impl Las {
     /// assert_eq!(expected, &result[..3]);
     /// ```
     pub fn headers_and_desc(&self) -> Vec<(String, String)> {
-        property(self.blob.as_str(), "~C")
-            .unwrap_or_default()
+        self.curve_params()
             .into_iter()
             .map(|(title, body)| (title, body.description))
             .collect()
     }
 
-    /// Returns `HashMap<String, WellProp>` containing all the `WellProp`(s) in a ~C (curve) section
+    /// Returns `HeaderSection` containing all the `WellProp`(s) in a ~C (curve) section, in file order
     ///
     /// ## Example
     ///
@@ -229,11 +959,19 @@ impl Las {
     ///     curve_section.get("SFLU").unwrap()
     /// );
     /// ```
-    pub fn curve_params(&self) -> HashMap<String, WellProp> {
-        property(&self.blob, "~C").unwrap_or_default()
+    pub fn curve_params(&self) -> HeaderSection {
+        self.curve_section().cloned().unwrap_or_default()
     }
 
-    /// Returns `HashMap<String, WellProp>` containing all the `WellProp`(s) in a ~W (well) section
+    /// Returns `HeaderSection` containing all the `WellProp`(s) in a ~C (curve) section,
+    /// or `LasError::MissingSection` if there is no ~C section
+    pub fn try_curve_params(&self) -> Result<HeaderSection, LasError> {
+        self.curve_section()
+            .cloned()
+            .ok_or_else(|| LasError::MissingSection("~C".to_owned()))
+    }
+
+    /// Returns `HeaderSection` containing all the `WellProp`(s) in a ~W (well) section, in file order
     ///
     /// ## Example
     ///
@@ -246,11 +984,41 @@ impl Las {
     ///     well_section.get("STOP").unwrap()
     /// );
     /// ```
-    pub fn well_info(&self) -> HashMap<String, WellProp> {
-        property(&self.blob, "~W").unwrap_or_default()
+    pub fn well_info(&self) -> HeaderSection {
+        self.document.well.clone().unwrap_or_default()
+    }
+
+    /// Returns `HeaderSection` containing all the `WellProp`(s) in a ~W (well) section,
+    /// or `LasError::MissingSection` if there is no ~W section
+    pub fn try_well_info(&self) -> Result<HeaderSection, LasError> {
+        self.document
+            .well
+            .clone()
+            .ok_or_else(|| LasError::MissingSection("~W".to_owned()))
+    }
+
+    /// Returns `WellHeader` - the entries of ~W (well) section as typed fields,
+    /// every field `None` if there is no ~W section
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let log = Las::new("./sample/example.las");
+    /// let header = log.well_header();
+    /// assert_eq!(header.stop.unwrap().value, 1669.75);
+    /// assert_eq!(header.company.as_deref(), Some("ANY OIL COMPANY INC."));
+    /// assert_eq!(header.null, Some(-999.25));
+    /// ```
+    pub fn well_header(&self) -> WellHeader {
+        self.document
+            .well
+            .as_ref()
+            .map(WellHeader::new)
+            .unwrap_or_default()
     }
 
-    /// Returns `HashMap<String, WellProp>` containing all the `WellProp`(s) in a ~P (parameter) section
+    /// Returns `HeaderSection` containing all the `WellProp`(s) in a ~P (parameter) section, in file order
     ///
     /// ## Example
     ///
@@ -262,8 +1030,19 @@ impl Las {
     ///     &WellProp::new("", "MUD TYPE", "GEL CHEM"),
     ///     params.get("MUD").unwrap()
     /// );
-    pub fn log_params(&self) -> HashMap<String, WellProp> {
-        property(&self.blob, "~P").unwrap_or_default()
+    /// ```
+    pub fn log_params(&self) -> HeaderSection {
+        self.log()
+            .and_then(|l| l.parameters.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns `HeaderSection` containing all the `WellProp`(s) in a ~P (parameter) section,
+    /// or `LasError::MissingSection` if there is no ~P section
+    pub fn try_log_params(&self) -> Result<HeaderSection, LasError> {
+        self.log()
+            .and_then(|l| l.parameters.clone())
+            .ok_or_else(|| LasError::MissingSection("~P".to_owned()))
     }
 
     /// Returns a `String` representing extra information in ~O (other) section
@@ -279,19 +1058,381 @@ impl Las {
     /// assert_eq!(log.other(), expected.join("\n").to_string());
     /// ```
     pub fn other(&self) -> String {
-        self.blob
-            .splitn(2, "~O")
-            .nth(1)
-            .unwrap_or("")
-            .splitn(2, "~")
-            .nth(0)
-            .map(|x| remove_comment(x))
-            .unwrap_or(vec![])
-            .into_iter()
-            .skip(1)
-            .map(|x| x.to_string())
-            .collect::<Vec<_>>()
-            .join("\n")
+        self.document.other.clone().unwrap_or_default()
+    }
+
+    /// Sets the `WellProp` of a mnemonic in ~W (well) section, in place if the mnemonic exists,
+    /// appended otherwise. Returns the replaced `WellProp`, `None` if the mnemonic is new,
+    /// or `LasError::InvalidMnemonic` if the mnemonic cannot be written
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the entry, e.g. `COMP`
+    ///
+    /// `prop` - unit, value and description of the entry
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{Las, WellProp};
+    /// let mut log = Las::new("./sample/example.las");
+    /// let prop = WellProp::new("", "COMPANY", "ACME");
+    /// let old = log.set_well_prop("COMP", prop.clone()).unwrap().unwrap();
+    /// assert_eq!(old.value, "ANY OIL COMPANY INC.");
+    /// assert_eq!(log.well_info().get("COMP"), Some(&prop));
+    /// ```
+    pub fn set_well_prop(
+        &mut self,
+        mnemonic: &str,
+        prop: WellProp,
+    ) -> Result<Option<WellProp>, LasError> {
+        check_mnemonic(mnemonic)?;
+        let well = self.document.well.get_or_insert_with(HeaderSection::new);
+        Ok(well.insert(mnemonic.to_owned(), prop))
+    }
+
+    /// Removes the entry with the given mnemonic from ~W (well) section,
+    /// returns its `WellProp`, `None` if absent
+    pub fn remove_well_prop(&mut self, mnemonic: &str) -> Option<WellProp> {
+        self.document.well.as_mut()?.remove(mnemonic)
+    }
+
+    /// Sets the `WellProp` of a mnemonic in ~P (parameter) section, in place if the mnemonic
+    /// exists, appended otherwise. Returns the replaced `WellProp`, `None` if the mnemonic is new,
+    /// or `LasError::InvalidMnemonic` if the mnemonic cannot be written
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the entry, e.g. `BHT`
+    ///
+    /// `prop` - unit, value and description of the entry
+    pub fn set_param(
+        &mut self,
+        mnemonic: &str,
+        prop: WellProp,
+    ) -> Result<Option<WellProp>, LasError> {
+        check_mnemonic(mnemonic)?;
+        let log = self.document.data_set_mut("Log");
+        let parameters = log.parameters.get_or_insert_with(HeaderSection::new);
+        Ok(parameters.insert(mnemonic.to_owned(), prop))
+    }
+
+    /// Removes the entry with the given mnemonic from ~P (parameter) section,
+    /// returns its `WellProp`, `None` if absent
+    pub fn remove_param(&mut self, mnemonic: &str) -> Option<WellProp> {
+        let log = self.log_mut()?;
+        log.parameters.as_mut()?.remove(mnemonic)
+    }
+
+    /// Sets the `WellProp` of a curve in ~C (curve) section, returns the replaced `WellProp`,
+    /// `None` if the curve had no entry in ~C section,
+    /// or `LasError::UnknownCurve` if no curve has the given mnemonic.
+    /// Curves are added with `add_curve` and removed with `drop_curve`
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the curve, e.g. `GR`
+    ///
+    /// `prop` - unit, API code and description of the curve
+    pub fn set_curve_prop(
+        &mut self,
+        mnemonic: &str,
+        prop: WellProp,
+    ) -> Result<Option<WellProp>, LasError> {
+        let log = self.curve_set_mut(mnemonic)?;
+        let definitions = log.definitions.get_or_insert_with(HeaderSection::new);
+        Ok(definitions.insert(mnemonic.to_owned(), prop))
+    }
+
+    /// Changes the mnemonic of a curve keeping its position and values
+    ///
+    /// Returns `LasError::UnknownCurve` if no curve has the mnemonic,
+    /// `LasError::InvalidMnemonic` if the new mnemonic cannot be written,
+    /// or `LasError::DuplicateCurve` if another curve has the new mnemonic
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - current mnemonic of the curve
+    ///
+    /// `new` - new mnemonic of the curve
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let mut log = Las::new("./sample/example.las");
+    /// log.rename_curve("ILD", "RESD").unwrap();
+    /// assert_eq!(log.headers()[7], "RESD");
+    /// assert_eq!(log.try_column("RESD").unwrap()[0], 105.6);
+    /// assert!(log.rename_curve("RESD", "ILM").is_err());
+    /// ```
+    pub fn rename_curve(&mut self, mnemonic: &str, new: &str) -> Result<(), LasError> {
+        check_mnemonic(new)?;
+        if mnemonic != new && self.titles().iter().any(|title| title == new) {
+            return Err(LasError::DuplicateCurve(new.to_owned()));
+        }
+        let log = self.curve_set_mut(mnemonic)?;
+        for title in log.headers.iter_mut().filter(|title| *title == mnemonic) {
+            *title = new.to_owned();
+        }
+        if let Some(definitions) = log.definitions.as_mut() {
+            definitions.rename(mnemonic, new.to_owned());
+        }
+        Ok(())
+    }
+
+    /// Removes a curve from ~C (curve) section together with its column in ~A (data) section,
+    /// returns its `WellProp`, `None` if the curve had no entry in ~C section,
+    /// or `LasError::UnknownCurve` if no curve has the given mnemonic
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the curve, e.g. `GR`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let mut log = Las::new("./sample/example.las");
+    /// let prop = log.drop_curve("DT").unwrap().unwrap();
+    /// assert_eq!(prop.description, "SONIC TRANSIT TIME");
+    /// assert_eq!(log.column_count(), 7);
+    /// assert_eq!(log.data()[0].len(), 7);
+    /// ```
+    pub fn drop_curve(&mut self, mnemonic: &str) -> Result<Option<WellProp>, LasError> {
+        let log = self.curve_set_mut(mnemonic)?;
+        let position = log.headers.iter().position(|title| title == mnemonic);
+        let position = position.ok_or_else(|| LasError::UnknownCurve(mnemonic.to_owned()))?;
+        log.headers.remove(position);
+        let prop = log.definitions.as_mut().and_then(|d| d.remove(mnemonic));
+        if let Some(data) = log.data.as_mut() {
+            if position < data.columns.len() {
+                data.columns.remove(position);
+            }
+            if position < data.types.len() {
+                data.types.remove(position);
+            }
+            // text is kept by column position, columns after the dropped one move left
+            data.text = data
+                .text
+                .drain()
+                .filter(|(column, _)| *column != position)
+                .map(|(column, values)| match column > position {
+                    true => (column - 1, values),
+                    false => (column, values),
+                })
+                .collect();
+        }
+        Ok(prop)
+    }
+
+    /// Adds a curve at the end of ~C (curve) section with its values as a new column
+    /// of ~A (data) section
+    ///
+    /// Returns `LasError::InvalidMnemonic` if the mnemonic cannot be written,
+    /// `LasError::DuplicateCurve` if a curve has the mnemonic already,
+    /// or `LasError::LengthMismatch` if there is not one value per row
+    ///
+    /// ## Arguments
+    ///
+    /// `mnemonic` - mnemonic of the curve, e.g. `VSH`
+    ///
+    /// `prop` - unit, API code and description of the curve
+    ///
+    /// `values` - values of the curve, one per row, `f64::NAN` for missing values
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{Las, WellProp};
+    /// let mut log = Las::new("./sample/example.las");
+    /// let prop = WellProp::new("V/V", "SHALE VOLUME", "");
+    /// log.add_curve("VSH", prop, vec![0.1, 0.2, 0.3, 0.4]).unwrap();
+    /// assert_eq!(log.headers().last().unwrap(), "VSH");
+    /// assert_eq!(log.try_column("VSH").unwrap(), vec![0.1, 0.2, 0.3, 0.4]);
+    ///
+    /// let prop = WellProp::new("", "TOO SHORT", "");
+    /// assert!(log.add_curve("SHORT", prop, vec![0.1]).is_err());
+    /// ```
+    pub fn add_curve(
+        &mut self,
+        mnemonic: &str,
+        prop: WellProp,
+        values: Vec<f64>,
+    ) -> Result<(), LasError> {
+        check_mnemonic(mnemonic)?;
+        if self.titles().iter().any(|title| title == mnemonic) {
+            return Err(LasError::DuplicateCurve(mnemonic.to_owned()));
+        }
+        if values.len() != self.row_count() {
+            return Err(LasError::LengthMismatch {
+                expected: self.row_count(),
+                found: values.len(),
+            });
+        }
+        self.curve_positions.take();
+        let log = self.document.data_set_mut("Log");
+        log.headers.push(mnemonic.to_owned());
+        let definitions = log.definitions.get_or_insert_with(HeaderSection::new);
+        definitions.insert(mnemonic.to_owned(), prop);
+        let data = log.data.get_or_insert_with(DataSection::default);
+        data.row_count = values.len();
+        data.columns.push(values);
+        data.types.push(CurveType::Float);
+        Ok(())
+    }
+
+    /// Replaces the text of ~O (other) section, added if absent
+    ///
+    /// ## Arguments
+    ///
+    /// `text` - new text of the section
+    pub fn set_other(&mut self, text: &str) {
+        self.document.other = Some(text.to_owned());
+    }
+
+    /// Writes ~A (data) section to the given writer
+    ///
+    /// Missing values are written as the `NULL` value of ~W (well) section, `-999.25` if absent,
+    /// values of string and datetime curves as read
+    ///
+    /// ## Arguments
+    ///
+    /// `writer` - destination of the section
+    ///
+    /// `options` - set `wrap` to write every depth step over multiple lines of at most 80 characters
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{Las, WriteOptions};
+    /// let log = Las::new("./sample/example.las");
+    /// let mut out = vec![];
+    /// let options = WriteOptions {
+    ///     wrap: true,
+    ///     ..WriteOptions::default()
+    /// };
+    /// log.write_data(&mut out, &options).unwrap();
+    /// let out = String::from_utf8(out).unwrap();
+    /// assert_eq!(out.lines().nth(1), Some("1670.0"));
+    /// assert_eq!(
+    ///     out.lines().nth(2),
+    ///     Some("123.45 2550.0 0.45 123.45 123.45 110.2 105.6")
+    /// );
+    /// ```
+    pub fn write_data<W: Write>(&self, writer: W, options: &WriteOptions) -> Result<(), LasError> {
+        let data = self.values();
+        let data = writer::Data {
+            headers: self.titles(),
+            columns: data.map(|d| d.columns.as_slice()).unwrap_or_default(),
+            row_count: self.row_count(),
+            types: data.map(|d| d.types.as_slice()).unwrap_or_default(),
+            text: data.map(|d| &d.text),
+            null: self.null_value(),
+            dlm: Delimiter::Space,
+        };
+        let mut out = vec![];
+        writer::write_data(&mut out, &data, options)?;
+        // a byte order mark only starts a file
+        let encoding = match options.encoding.unwrap_or(self.encoding) {
+            Encoding::Utf8Bom => Encoding::Utf8,
+            encoding => encoding,
+        };
+        self.write_encoded(writer, &out, encoding)
+    }
+
+    /// Writes the file as LAS 2.0 to the given writer - ~V, ~W, ~C, ~P, ~O and ~A sections
+    ///
+    /// Entries are written in file order with aligned units, values and descriptions.
+    /// Missing values are written as the `NULL` value of ~W (well) section, added as `-999.25`
+    /// if absent. Only the `Log` data set of a LAS 3.0 file is written.
+    ///
+    /// With `lossless` set the file is written as read instead, comments, spacing and
+    /// line endings included, with only the lines of changed entries rewritten
+    /// and sections missing from the file added in LAS 2.0 order.
+    /// A `NULL` entry of `-999.25` is added if a data section is rewritten without one.
+    /// `LasError::PartialDocument` is returned if the file was parsed with a selection
+    /// of curves or an index range, and in both modes for a `Las` read by `open_headers`.
+    /// The file is written in the encoding it was read with unless `encoding` is set, and
+    /// `LasError::Unencodable` is returned for a character that encoding cannot represent
+    ///
+    /// ## Arguments
+    ///
+    /// `writer` - destination of the file
+    ///
+    /// `options` - wrap mode and precision of ~A (data) section
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{Las, WriteOptions};
+    /// let log = Las::new("./sample/example.las");
+    /// let mut out = vec![];
+    /// let options = WriteOptions {
+    ///     precision: Some(3),
+    ///     ..WriteOptions::default()
+    /// };
+    /// log.write_to(&mut out, &options).unwrap();
+    /// let copy = Las::from_bytes(&out).unwrap();
+    /// assert_eq!(copy.well_info(), log.well_info());
+    /// assert_eq!(copy.data(), log.data());
+    /// ```
+    pub fn write_to<W: Write>(&self, writer: W, options: &WriteOptions) -> Result<(), LasError> {
+        // the data left unread would be written as removed
+        if self.headers_only {
+            return Err(LasError::PartialDocument);
+        }
+        let mut out = vec![];
+        if options.lossless {
+            // the lines of the curves and rows left out would be written as removed
+            if self.options.curves.is_some() || self.options.index_range.is_some() {
+                return Err(LasError::PartialDocument);
+            }
+            let original = LasDocument::parse_with(&self.blob, &self.options)?;
+            syntax::write_lossless(&mut out, &self.blob, &original, &self.document, options)?;
+        } else {
+            writer::write_las(&mut out, &self.document, options)?;
+        }
+        self.write_encoded(writer, &out, options.encoding.unwrap_or(self.encoding))
+    }
+
+    // Writes UTF-8 text to writer in the given encoding
+    fn write_encoded<W: Write>(
+        &self,
+        mut writer: W,
+        text: &[u8],
+        encoding: Encoding,
+    ) -> Result<(), LasError> {
+        match encoding {
+            Encoding::Utf8 => writer.write_all(text)?,
+            encoding => writer.write_all(&encoding.encode(&String::from_utf8_lossy(text))?)?,
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Saves the file as LAS 2.0 to the given path, see `write_to`
+    ///
+    /// ## Arguments
+    ///
+    /// `path` - Path of the saved file
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<(), LasError> {
+        self.save_with(path, &WriteOptions::default())
+    }
+
+    /// Saves the file as LAS 2.0 to the given path with the given write options, see `write_to`
+    ///
+    /// ## Arguments
+    ///
+    /// `path` - Path of the saved file
+    ///
+    /// `options` - wrap mode and precision of ~A (data) section
+    pub fn save_with<T: AsRef<Path>>(
+        &self,
+        path: T,
+        options: &WriteOptions,
+    ) -> Result<(), LasError> {
+        self.write_to(File::create(path.as_ref())?, options)
     }
 
     /// Converts file to csv and saves it to the current directory
@@ -299,20 +1440,61 @@ impl Las {
     ///
     /// `filename` - string slice, the name used to save the csv file
     pub fn to_csv(&self, filename: &str) {
-        let f = File::create(format!("{}.csv", filename)).expect("Unable to create csv file");
+        self.try_to_csv(filename).expect("Unable to write csv file");
+    }
+
+    /// Converts file to csv and saves it to the current directory,
+    /// returns `LasError::Io` if the file cannot be created or written
+    /// ## Arguments
+    ///
+    /// `filename` - string slice, the name used to save the csv file
+    pub fn try_to_csv(&self, filename: &str) -> Result<(), LasError> {
+        let f = File::create(format!("{}.csv", filename))?;
         let mut f = BufWriter::new(f);
-        let mut headers = self.headers().join(",");
-        headers.push_str("\n");
-        f.write(headers.as_bytes())
-            .expect("Unable to write headers");
+        let mut headers = self.titles().join(",");
+        headers.push('\n');
+        f.write_all(headers.as_bytes())?;
         let data = self
-            .data()
+            .values()
+            .map(DataSection::rows)
             .into_iter()
+            .flatten()
             .map(|x| x.into_iter().map(|d| d.to_string()))
             .map(|x| x.collect::<Vec<_>>().join(","))
             .collect::<Vec<_>>()
             .join("\n");
-        f.write_all(data.as_bytes())
-            .expect("Unable to write data to file");
+        f.write_all(data.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl FromStr for Las {
+    type Err = LasError;
+
+    /// Returns a `Las` parsed from the content of a well log file
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::Las;
+    /// let raw = std::fs::read_to_string("./sample/example.las").unwrap();
+    /// let log: Las = raw.parse().unwrap();
+    /// assert_eq!(log.row_count(), 4);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_blob(s.to_owned(), Encoding::Utf8, &ParseOptions::default())
+    }
+}
+
+// Returns `LasError::InvalidMnemonic` if mnemonic would not be read back as written
+fn check_mnemonic(mnemonic: &str) -> Result<(), LasError> {
+    // the occurrence number of a repeated mnemonic is not written, e.g. `GR:1`
+    let mnemonic = writer::mnemonic(mnemonic);
+    let invalid = mnemonic.is_empty()
+        || mnemonic.starts_with(['~', '#'])
+        || mnemonic.contains(|c: char| c.is_whitespace() || ".:{}|".contains(c));
+    match invalid {
+        true => Err(LasError::InvalidMnemonic(mnemonic.to_owned())),
+        false => Ok(()),
     }
 }
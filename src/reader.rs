@@ -0,0 +1,407 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::document::{
+    classify, declared_types, in_window, missing_value, row_complete, select, selected, SectionKind,
+};
+use crate::util::without_bom;
+use crate::{CurveType, Encoding, Las, LasDocument, LasError, ParseOptions};
+
+// A value of a row - its span in the text of the row and its position in the file
+struct Value {
+    span: Range<usize>,
+    line: usize,
+    column: usize,
+}
+
+/// Streaming reader of a well log file - parses the header sections on construction,
+/// then yields the rows of ~A (data) section one at a time
+///
+/// Only the current row is held in memory, whatever the size of the file.
+/// Values of string and datetime curves are yielded as missing values, and a row that
+/// does not hold one value per curve is yielded as `LasError::MalformedRow`, reading
+/// carries on with the next row. Sections after ~A section are not read
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::LasReader;
+/// let reader = LasReader::open("./sample/example.las").unwrap();
+/// assert_eq!(reader.headers()[1], "DT");
+/// let max_dt = reader
+///     .map(|row| row.unwrap()[1])
+///     .fold(f64::MIN, f64::max);
+/// assert_eq!(max_dt, 123.45);
+/// ```
+pub struct LasReader<R> {
+    reader: R,
+    document: LasDocument,
+    encoding: Encoding,
+    options: ParseOptions,
+    // number of values in a row of the file
+    columns: usize,
+    // positions of the values kept, every value if `None`
+    selected: Option<Vec<usize>>,
+    // `true` for string and datetime curves by position
+    text: Vec<bool>,
+    data_offset: Option<u64>,
+    // line number of the last line read
+    line: usize,
+    // values of the row being read, the text of its lines and the line it starts on,
+    // reused from row to row
+    row: Vec<Value>,
+    row_text: String,
+    row_line: usize,
+    // set once the end of ~A section is reached
+    done: bool,
+    // bytes of the line being read
+    buf: Vec<u8>,
+}
+
+impl LasReader<BufReader<File>> {
+    /// Returns a `LasReader` of a las file with the given path,
+    /// or `LasError::Io` if the file cannot be read
+    ///
+    /// ## Arguments
+    ///
+    /// `path` - Path to well log file
+    pub fn open<T: AsRef<Path>>(path: T) -> Result<Self, LasError> {
+        Self::open_with(path, &ParseOptions::default())
+    }
+
+    /// Returns a `LasReader` of a las file with the given path and parse options,
+    /// see `with_options`
+    ///
+    /// ## Arguments
+    ///
+    /// `path` - Path to well log file
+    ///
+    /// `options` - parse options
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::{LasReader, ParseOptions};
+    /// let options = ParseOptions {
+    ///     curves: Some(vec!["DEPT".to_owned(), "ILD".to_owned()]),
+    ///     index_range: Some((1669.8, 1670.0)),
+    ///     ..ParseOptions::default()
+    /// };
+    /// let rows = LasReader::open_with("./sample/example.las", &options).unwrap();
+    /// let rows = rows.collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert_eq!(rows, vec![vec![1670.0, 105.6], vec![1669.875, 105.6]]);
+    /// ```
+    pub fn open_with<T: AsRef<Path>>(path: T, options: &ParseOptions) -> Result<Self, LasError> {
+        Self::with_options(BufReader::new(File::open(path.as_ref())?), options)
+    }
+}
+
+impl<R: BufRead> LasReader<R> {
+    /// Returns a `LasReader` of any buffered reader, reading up to the title of ~A (data)
+    /// section, or `LasError::Io` if reading fails
+    ///
+    /// ## Arguments
+    ///
+    /// `reader` - source of the well log content
+    pub fn new(reader: R) -> Result<Self, LasError> {
+        Self::with_options(reader, &ParseOptions::default())
+    }
+
+    /// Returns a `LasReader` of any buffered reader with the given parse options,
+    /// reading up to the title of ~A (data) section
+    ///
+    /// The encoding is detected from the header sections unless set in options, a data line
+    /// that is not valid UTF-8 is then read as Latin-1 or Windows-1252.
+    /// In strict mode a value that is not a number is yielded as `LasError::InvalidValue`.
+    /// Rows hold only the curves of `ParseOptions::curves`, and rows outside
+    /// `ParseOptions::index_range` are skipped
+    ///
+    /// ## Arguments
+    ///
+    /// `reader` - source of the well log content
+    ///
+    /// `options` - parse options
+    pub fn with_options(mut reader: R, options: &ParseOptions) -> Result<Self, LasError> {
+        let header = read_header(&mut reader)?;
+        let encoding = options
+            .encoding
+            .unwrap_or_else(|| Encoding::detect(&header.bytes));
+        let raw = encoding.decode(&header.bytes[..header.bytes.len() - header.title])?;
+        Self::from_parts(
+            reader,
+            &raw,
+            encoding,
+            options,
+            header.lines,
+            header.offset(),
+        )
+    }
+
+    /// Returns a `LasReader` of the rows of a `Las` read with `Las::open_headers`,
+    /// from a reader positioned at `Las::data_offset`
+    ///
+    /// ## Arguments
+    ///
+    /// `reader` - source of the well log content, past the title of ~A (data) section
+    ///
+    /// `las` - the header sections of the source
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use std::io::{BufReader, Seek, SeekFrom};
+    /// use lasrs::{Las, LasReader};
+    /// let las = Las::open_headers("./sample/example.las").unwrap();
+    /// let mut file = std::fs::File::open("./sample/example.las").unwrap();
+    /// file.seek(SeekFrom::Start(las.data_offset().unwrap())).unwrap();
+    /// let rows = LasReader::resume(BufReader::new(file), &las).unwrap();
+    /// assert_eq!(rows.count(), 4);
+    /// ```
+    ///
+    /// Returns `LasError::InvalidValue` in strict mode if a LAS 3.0 data section preceding
+    /// ~A section holds a value that is not a number
+    pub fn resume(reader: R, las: &Las) -> Result<Self, LasError> {
+        let (line, index, offset) = match las.data_start() {
+            Some((line, index, offset)) => (line, index, Some(offset)),
+            None => (0, las.blob.len(), None),
+        };
+        Self::from_parts(
+            reader,
+            &las.blob[..index],
+            las.encoding,
+            &las.options,
+            line,
+            offset,
+        )
+    }
+
+    // Returns a reader of the rows following the header sections in raw_str
+    fn from_parts(
+        reader: R,
+        raw_str: &str,
+        encoding: Encoding,
+        options: &ParseOptions,
+        line: usize,
+        data_offset: Option<u64>,
+    ) -> Result<Self, LasError> {
+        // every curve is parsed to know the positions of the values kept
+        let every_curve = ParseOptions {
+            curves: None,
+            ..options.clone()
+        };
+        let mut document = LasDocument::parse_with(raw_str, &every_curve)?;
+        let (columns, selected, text) = match document.log() {
+            Some(set) => {
+                let text = declared_types(set, options)
+                    .into_iter()
+                    .map(|kind| matches!(kind, Some(CurveType::String) | Some(CurveType::DateTime)))
+                    .collect();
+                (set.headers.len(), selected(set, options), text)
+            }
+            None => (0, None, vec![]),
+        };
+        if let Some(keep) = &selected {
+            select(document.data_set_mut("Log"), keep);
+        }
+        Ok(Self {
+            reader,
+            document,
+            encoding,
+            options: options.clone(),
+            columns,
+            selected,
+            text,
+            data_offset,
+            line,
+            row: vec![],
+            row_text: String::new(),
+            row_line: line,
+            done: data_offset.is_none(),
+            buf: vec![],
+        })
+    }
+
+    /// Returns the `LasDocument` of the header sections, without data
+    pub fn document(&self) -> &LasDocument {
+        &self.document
+    }
+
+    /// Returns the titles of the curves in ~C (curve) section, in the order of the row values
+    pub fn headers(&self) -> &[String] {
+        self.document
+            .log()
+            .map(|l| l.headers.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the encoding the file is read with
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Returns the byte offset of the line following the title of ~A (data) section,
+    /// `None` if there is no ~A section
+    pub fn data_offset(&self) -> Option<u64> {
+        self.data_offset
+    }
+
+    // Returns the values kept of the row read as numbers, `None` if the row is outside
+    // the index range of the options
+    fn values(&self) -> Option<Result<Vec<f64>, LasError>> {
+        let row = &self.row;
+        if row.len() != self.columns {
+            return Some(Err(LasError::MalformedRow {
+                line: self.row_line,
+                expected: self.columns,
+                found: row.len(),
+            }));
+        }
+        let text = |value: &Value| &self.row_text[value.span.clone()];
+        let null = self.document.null_value();
+        if !in_window(&self.options, row.first().map(text), null) {
+            return None;
+        }
+        let missing = missing_value(&self.options, null);
+        let convert = |column: usize| {
+            let value = &row[column];
+            match text(value).parse::<f64>() {
+                Ok(number) if Some(number) == null => Ok(missing),
+                Ok(number) => Ok(number),
+                Err(_) if self.options.strict && !self.text[column] => {
+                    Err(LasError::InvalidValue {
+                        line: value.line,
+                        column: value.column,
+                        value: text(value).to_owned(),
+                    })
+                }
+                Err(_) => Ok(missing),
+            }
+        };
+        Some(match &self.selected {
+            Some(keep) => keep.iter().map(|&column| convert(column)).collect(),
+            None => (0..row.len()).map(convert).collect(),
+        })
+    }
+
+    // Reads the next row into `row` and `row_text`, returns `false` at the end of ~A section
+    fn next_row(&mut self) -> Result<bool, LasError> {
+        let (wrap, dlm) = self
+            .document
+            .version
+            .as_ref()
+            .map(|v| (v.wrap, v.dlm))
+            .unwrap_or_default();
+        self.row.clear();
+        self.row_text.clear();
+        while !self.done {
+            self.buf.clear();
+            match self.reader.read_until(b'\n', &mut self.buf) {
+                Ok(0) => {
+                    self.done = true;
+                    break;
+                }
+                Ok(_) => self.line += 1,
+                Err(err) => {
+                    self.done = true;
+                    return Err(err.into());
+                }
+            }
+            // lines are decoded after the previous lines of the row
+            let start = self.row_text.len();
+            if let Err(err) = self.encoding.decode_into(&self.buf, &mut self.row_text) {
+                // a detected encoding is read from the headers only, a data line that is
+                // not UTF-8 is read as Latin-1 or Windows-1252 like `Encoding::detect` does
+                if self.options.encoding.is_some() {
+                    return Err(err);
+                }
+                self.row_text.truncate(start);
+                Encoding::detect(&self.buf).decode_into(&self.buf, &mut self.row_text)?;
+            }
+            let line = self.row_text[start..].trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('~') {
+                self.done = line.starts_with('~');
+                self.row_text.truncate(start);
+                continue;
+            }
+            if self.row.is_empty() {
+                self.row_line = self.line;
+            }
+            let (number, base) = (self.line, self.row_text.as_ptr() as usize);
+            self.row.extend(dlm.split(line).into_iter().map(|value| {
+                let offset = value.as_ptr() as usize - base;
+                Value {
+                    span: offset..offset + value.len(),
+                    line: number,
+                    column: offset - start + 1,
+                }
+            }));
+            if row_complete(wrap, self.row.len(), self.columns) {
+                return Ok(true);
+            }
+        }
+        Ok(!self.row.is_empty())
+    }
+}
+
+impl<R: BufRead> Iterator for LasReader<R> {
+    type Item = Result<Vec<f64>, LasError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_row() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(err) => return Some(Err(err)),
+            }
+            if let Some(values) = self.values() {
+                return Some(values);
+            }
+        }
+    }
+}
+
+// Content of a well log file up to the title of ~A (data) section
+pub(crate) struct Header {
+    // content read, title line included
+    pub(crate) bytes: Vec<u8>,
+    // number of lines read
+    pub(crate) lines: usize,
+    // length of the title line, 0 if the end was reached without a ~A section
+    pub(crate) title: usize,
+}
+
+impl Header {
+    // Returns the byte offset of the line following the title, `None` without a title
+    pub(crate) fn offset(&self) -> Option<u64> {
+        Some(self.bytes.len() as u64).filter(|_| self.title > 0)
+    }
+}
+
+// Reads a well log file up to and including the title line of ~A (data) section
+pub(crate) fn read_header<R: BufRead>(reader: &mut R) -> Result<Header, LasError> {
+    let mut header = Header {
+        bytes: vec![],
+        lines: 0,
+        title: 0,
+    };
+    loop {
+        let start = header.bytes.len();
+        if reader.read_until(b'\n', &mut header.bytes)? == 0 {
+            return Ok(header);
+        }
+        header.lines += 1;
+        // titles are ASCII in every supported encoding
+        let line = String::from_utf8_lossy(&header.bytes[start..]);
+        let line = match start {
+            0 => without_bom(&line),
+            _ => &line,
+        };
+        let title = line.trim_start().starts_with('~');
+        if title && matches!(classify(line), (name, SectionKind::Data, _) if name == "Log") {
+            header.title = header.bytes.len() - start;
+            return Ok(header);
+        }
+    }
+}
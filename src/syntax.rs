@@ -0,0 +1,573 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::document::{classify, SectionKind};
+use crate::util::{property, swapped, without_bom, Fields};
+use crate::writer::{self, Data};
+use crate::{DataSection, DataSet, HeaderSection, LasDocument, WellProp, WriteOptions};
+
+/// Represents the kind of a line in a well log file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// section title, starting with `~`
+    Title,
+    /// line starting with `#`
+    Comment,
+    /// empty line or line of spaces
+    Blank,
+    /// entry, data row or text of a section
+    Content,
+}
+
+/// Represents a line of a well log file as written
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxLine {
+    /// text of the line, without line ending
+    pub text: String,
+    /// line ending as written - `\n`, `\r\n`, or empty for a last line without one
+    pub ending: String,
+    /// kind of the line
+    pub kind: LineKind,
+}
+
+impl SyntaxLine {
+    // Returns a line of text, classified by its first character
+    fn new(text: &str, ending: &str) -> Self {
+        let trimmed = text.trim_start();
+        let kind = if trimmed.starts_with('~') {
+            LineKind::Title
+        } else if trimmed.starts_with('#') {
+            LineKind::Comment
+        } else if trimmed.is_empty() {
+            LineKind::Blank
+        } else {
+            LineKind::Content
+        };
+        Self {
+            text: text.to_owned(),
+            ending: ending.to_owned(),
+            kind,
+        }
+    }
+}
+
+/// Represents a section of a well log file as written, title line included
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxSection {
+    /// line number (starting from 1) of the section title in the file
+    pub line: usize,
+    /// lines of the section, starting with its title
+    pub lines: Vec<SyntaxLine>,
+}
+
+impl SyntaxSection {
+    /// Returns the title line of the section, e.g. `~Well Information`
+    pub fn title(&self) -> &str {
+        self.lines.first().map(|l| l.text.as_str()).unwrap_or("")
+    }
+
+    // Returns the content of the section as written
+    fn content(&self) -> String {
+        self.lines
+            .iter()
+            .map(|l| format!("{}{}", l.text, l.ending))
+            .collect()
+    }
+
+    // Returns the line ending used by the section
+    fn ending(&self) -> &str {
+        match self.lines.iter().find(|l| !l.ending.is_empty()) {
+            Some(line) => &line.ending,
+            None => "\n",
+        }
+    }
+}
+
+/// Lossless concrete syntax tree of a well log file - every line as written,
+/// comments, spacing and line endings included
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::{LineKind, SyntaxTree};
+/// let raw = std::fs::read_to_string("./sample/A10.las").unwrap();
+/// let tree = SyntaxTree::parse(&raw);
+/// assert_eq!(tree.preamble[0].text, "# LAS format log file from PETREL");
+/// assert_eq!(tree.preamble[0].kind, LineKind::Comment);
+/// assert_eq!(tree.sections[1].title(), "~Well");
+/// assert_eq!(tree.to_string(), raw);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SyntaxTree {
+    /// `true` if the content starts with a UTF-8 byte order mark, which no line holds
+    pub bom: bool,
+    /// lines before the first section, usually comments
+    pub preamble: Vec<SyntaxLine>,
+    /// sections in the order they appear in the file
+    pub sections: Vec<SyntaxSection>,
+}
+
+impl SyntaxTree {
+    /// Returns the `SyntaxTree` of the content of a well log file
+    ///
+    /// ## Arguments
+    ///
+    /// `raw_str` - content of the well log file
+    pub fn parse(raw_str: &str) -> Self {
+        let body = without_bom(raw_str);
+        let mut tree = Self {
+            bom: body.len() < raw_str.len(),
+            ..Self::default()
+        };
+        for (i, line) in body.split_inclusive('\n').enumerate() {
+            let text = line.trim_end_matches(['\r', '\n']);
+            let line = SyntaxLine::new(text, &line[text.len()..]);
+            match tree.sections.last_mut() {
+                _ if line.kind == LineKind::Title => tree.sections.push(SyntaxSection {
+                    line: i + 1,
+                    lines: vec![line],
+                }),
+                Some(section) => section.lines.push(line),
+                None => tree.preamble.push(line),
+            }
+        }
+        tree
+    }
+
+    // Rewrites the lines of entries, text and data that differ between original,
+    // the document parsed from the tree, and current, leaving every other line as written
+    pub(crate) fn update(
+        &mut self,
+        original: &LasDocument,
+        current: &LasDocument,
+        options: &WriteOptions,
+    ) {
+        let las12 = original.las12();
+        // data sections are rewritten in the wrap mode of the file
+        let options = WriteOptions {
+            wrap: original.version.as_ref().is_some_and(|v| v.wrap),
+            ..options.clone()
+        };
+        // missing values of rewritten data sections are written as `DEFAULT_NULL`,
+        // declared by a `NULL` entry added to ~W (well) section
+        let changed = current
+            .data_sets
+            .iter()
+            .any(|new| match original.data_set(&new.name) {
+                Some(old) => !same_set_data(old, new),
+                None => new.data.is_some(),
+            });
+        let well = match &current.well {
+            Some(well) if changed && !well.contains_key("NULL") => {
+                let mut well = well.clone();
+                well.insert("NULL".to_owned(), writer::null_entry());
+                Some(well)
+            }
+            _ => None,
+        };
+        let well = well.as_ref().or(current.well.as_ref());
+        let mut seen: Vec<(String, SectionKind)> = vec![];
+        for section in self.sections.iter_mut() {
+            let content = section.content();
+            let (name, kind, _) = classify(&content);
+            // only the first occurrence of every section is parsed
+            if seen.contains(&(name.clone(), kind)) {
+                continue;
+            }
+            seen.push((name.clone(), kind));
+            let sets = (original.data_set(&name), current.data_set(&name));
+            match kind {
+                SectionKind::Well => {
+                    if let (Some(old), Some(new)) = (&original.well, well) {
+                        update_entries(section, &content, old, new, las12);
+                    }
+                }
+                SectionKind::Definition => {
+                    let old = sets.0.and_then(|s| s.definitions.as_ref());
+                    let new = sets.1.and_then(|s| s.definitions.as_ref());
+                    if let (Some(old), Some(new)) = (old, new) {
+                        update_entries(section, &content, old, new, false);
+                    }
+                }
+                SectionKind::Parameter => {
+                    let old = sets.0.and_then(|s| s.parameters.as_ref());
+                    let new = sets.1.and_then(|s| s.parameters.as_ref());
+                    if let (Some(old), Some(new)) = (old, new) {
+                        update_entries(section, &content, old, new, false);
+                    }
+                }
+                SectionKind::Other if original.other != current.other => {
+                    let ending = section.ending().to_owned();
+                    let text = current.other.as_deref().unwrap_or("");
+                    section.lines.truncate(1);
+                    section
+                        .lines
+                        .extend(text.lines().map(|l| SyntaxLine::new(l, &ending)));
+                }
+                SectionKind::Data => {
+                    let (Some(old), Some(new)) = sets else {
+                        continue;
+                    };
+                    if old.headers != new.headers {
+                        rename_columns(section, &old.headers, &new.headers);
+                    }
+                    if !same_set_data(old, new) {
+                        replace_data(section, &set_data(new, current), &options);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.add_missing(current, well, &seen, &options);
+    }
+
+    // Adds the sections of the `Log` data set, ~W and ~O sections that current holds
+    // and the file does not, in the LAS 2.0 order of the sections
+    fn add_missing(
+        &mut self,
+        current: &LasDocument,
+        well: Option<&HeaderSection>,
+        seen: &[(String, SectionKind)],
+        options: &WriteOptions,
+    ) {
+        let missing = |kind| !seen.contains(&("Log".to_owned(), kind));
+        let log = current.log();
+        let mut added: Vec<(SectionKind, Vec<String>)> = vec![];
+        if let Some(well) = well.filter(|_| missing(SectionKind::Well)) {
+            let lines = entry_section("~Well Information", well, current.las12());
+            added.push((SectionKind::Well, lines));
+        }
+        let definitions = log.and_then(|l| l.definitions.as_ref());
+        if let Some(definitions) = definitions.filter(|_| missing(SectionKind::Definition)) {
+            let lines = entry_section("~Curve Information", definitions, false);
+            added.push((SectionKind::Definition, lines));
+        }
+        let parameters = log.and_then(|l| l.parameters.as_ref());
+        if let Some(parameters) = parameters.filter(|_| missing(SectionKind::Parameter)) {
+            let lines = entry_section("~Parameter Information", parameters, false);
+            added.push((SectionKind::Parameter, lines));
+        }
+        if let Some(other) = current
+            .other
+            .as_ref()
+            .filter(|_| missing(SectionKind::Other))
+        {
+            let title = std::iter::once("~Other Information");
+            added.push((
+                SectionKind::Other,
+                title.chain(other.lines()).map(String::from).collect(),
+            ));
+        }
+        if let Some(set) = log.filter(|l| l.data.is_some() && missing(SectionKind::Data)) {
+            let mut out = vec![];
+            // writing to a Vec does not fail
+            let _ = writer::write_data(&mut out, &set_data(set, current), options);
+            let lines = String::from_utf8_lossy(&out)
+                .lines()
+                .map(String::from)
+                .collect();
+            added.push((SectionKind::Data, lines));
+        }
+        let ending = match self.sections.first() {
+            Some(section) => section.ending().to_owned(),
+            None => "\n".to_owned(),
+        };
+        for (kind, lines) in added {
+            let section = SyntaxSection {
+                line: 0,
+                lines: lines.iter().map(|l| SyntaxLine::new(l, &ending)).collect(),
+            };
+            // before the first section of the `Log` data set that follows it
+            let position = self.sections.iter().position(|s| {
+                let (name, next, _) = classify(&s.content());
+                name == "Log" && next != SectionKind::Unknown && rank(next) > rank(kind)
+            });
+            match position {
+                Some(position) => self.sections.insert(position, section),
+                None => {
+                    // the last line of the file may have no line ending
+                    if let Some(last) = self.sections.last_mut().and_then(|s| s.lines.last_mut()) {
+                        if last.ending.is_empty() {
+                            last.ending = ending.clone();
+                        }
+                    }
+                    self.sections.push(section);
+                }
+            }
+        }
+    }
+}
+
+// Returns the position of a section in a LAS 2.0 file - ~V, ~W, ~C, ~P, ~O then ~A
+fn rank(kind: SectionKind) -> usize {
+    match kind {
+        SectionKind::Version => 0,
+        SectionKind::Well => 1,
+        SectionKind::Definition => 2,
+        SectionKind::Parameter => 3,
+        SectionKind::Other => 4,
+        SectionKind::Data | SectionKind::Unknown => 5,
+    }
+}
+
+// Returns the values of the data section of a data set and how to write them
+fn set_data<'a>(set: &'a DataSet, doc: &LasDocument) -> Data<'a> {
+    let rows = set.data.as_ref();
+    Data {
+        headers: &set.headers,
+        columns: rows.map(|d| d.columns.as_slice()).unwrap_or_default(),
+        row_count: rows.map_or(0, |d| d.row_count),
+        types: rows.map(|d| d.types.as_slice()).unwrap_or_default(),
+        text: rows.map(|d| &d.text),
+        null: doc.null_value(),
+        dlm: doc.version.as_ref().map(|v| v.dlm).unwrap_or_default(),
+    }
+}
+
+// Returns the lines of a section of entries added to the file, title included
+fn entry_section(title: &str, section: &HeaderSection, las12: bool) -> Vec<String> {
+    let entries = section.iter().map(|(m, prop)| entry_line(m, prop, las12));
+    std::iter::once(title.to_owned()).chain(entries).collect()
+}
+
+impl fmt::Display for SyntaxTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.bom {
+            write!(f, "\u{FEFF}")?;
+        }
+        let sections = self.sections.iter().flat_map(|s| s.lines.iter());
+        for line in self.preamble.iter().chain(sections) {
+            write!(f, "{}{}", line.text, line.ending)?;
+        }
+        Ok(())
+    }
+}
+
+// Writes the file as read from source, with the changes between original and current
+pub(crate) fn write_lossless<W: Write>(
+    writer: &mut W,
+    source: &str,
+    original: &LasDocument,
+    current: &LasDocument,
+    options: &WriteOptions,
+) -> io::Result<()> {
+    let mut tree = SyntaxTree::parse(source);
+    tree.update(original, current, options);
+    write!(writer, "{}", tree)
+}
+
+// Returns `true` if both data sets hold the same number of curves and the same values
+fn same_set_data(old: &DataSet, new: &DataSet) -> bool {
+    old.headers.len() == new.headers.len()
+        && match (&old.data, &new.data) {
+            (Some(a), Some(b)) => same_data(a, b),
+            (a, b) => a == b,
+        }
+}
+
+// Returns `true` if both data sections hold the same values, NaN included
+fn same_data(a: &DataSection, b: &DataSection) -> bool {
+    let same_column = |(x, y): (&Vec<f64>, &Vec<f64>)| {
+        x.len() == y.len() && x.iter().zip(y).all(|(a, b)| a.to_bits() == b.to_bits())
+    };
+    a.row_count == b.row_count
+        && a.columns.len() == b.columns.len()
+        && a.columns.iter().zip(&b.columns).all(same_column)
+        && a.text == b.text
+}
+
+// Rewrites the title of a data section listing the curves, e.g. `~A  DEPT  GR`,
+// with the curves of new
+fn rename_columns(section: &mut SyntaxSection, old: &[String], new: &[String]) {
+    let Some(title) = section.lines.first_mut() else {
+        return;
+    };
+    let mut words = title.text.split_whitespace();
+    let signature = words.next().unwrap_or("~A").to_owned();
+    if words.eq(old.iter().map(|h| writer::mnemonic(h))) {
+        let new = new.iter().map(|h| writer::mnemonic(h)).collect::<Vec<_>>();
+        title.text = format!("{}  {}", signature, new.join(" "));
+    }
+}
+
+// Rewrites the body of a data section, keeping its title line
+fn replace_data(section: &mut SyntaxSection, data: &Data, options: &WriteOptions) {
+    let ending = section.ending().to_owned();
+    let mut out = vec![];
+    // writing to a Vec does not fail
+    let _ = writer::write_data(&mut out, data, options);
+    let out = String::from_utf8_lossy(&out);
+    section.lines.truncate(1);
+    section.lines.extend(
+        out.lines()
+            .skip(1)
+            .map(|line| SyntaxLine::new(line, &ending)),
+    );
+}
+
+// Rewrites the entry lines of a section that differ between old and new,
+// removes the lines of entries missing from new and appends entries missing from old
+fn update_entries(
+    section: &mut SyntaxSection,
+    content: &str,
+    old: &HeaderSection,
+    new: &HeaderSection,
+    las12: bool,
+) {
+    if old == new {
+        return;
+    }
+    // entries are the content lines after the title, in the order of `old`
+    let parsed = property(section.line, content, las12, &mut vec![]);
+    let mut entries = parsed.iter().enumerate();
+    let mut lines = vec![];
+    let mut last_entry = 0;
+    let mut dropped = 0;
+    let mut renamed = vec![];
+    for (i, line) in section.lines.drain(..).enumerate() {
+        if i == 0 || line.kind != LineKind::Content {
+            lines.push(line);
+            continue;
+        }
+        let Some((position, (mnemonic, prop))) = entries.next() else {
+            lines.push(line);
+            continue;
+        };
+        match new.get(mnemonic) {
+            Some(p) if p == prop => lines.push(line),
+            Some(p) => lines.push(SyntaxLine {
+                text: rewrite_entry(&line.text, prop, p, las12),
+                ..line
+            }),
+            None => {
+                // a new entry in place of one missing from new is the same entry renamed
+                let item = new.items().get(position - dropped);
+                match item.filter(|item| !parsed.contains_key(&item.mnemonic)) {
+                    Some(item) => {
+                        renamed.push(&item.mnemonic);
+                        let text = rename_entry(&line.text, &item.mnemonic);
+                        lines.push(SyntaxLine {
+                            text: rewrite_entry(&text, prop, &item.prop, las12),
+                            ..line
+                        });
+                    }
+                    None => {
+                        dropped += 1;
+                        continue;
+                    }
+                }
+            }
+        }
+        last_entry = lines.len();
+    }
+    let ending = lines
+        .iter()
+        .find(|l| !l.ending.is_empty())
+        .map(|l| l.ending.clone())
+        .unwrap_or_else(|| "\n".to_owned());
+    if last_entry == 0 {
+        last_entry = 1.min(lines.len());
+    }
+    let added = new
+        .iter()
+        .filter(|(mnemonic, _)| !parsed.contains_key(mnemonic) && !renamed.contains(mnemonic))
+        .map(|(mnemonic, prop)| SyntaxLine::new(&entry_line(mnemonic, prop, las12), &ending))
+        .collect::<Vec<_>>();
+    lines.splice(last_entry..last_entry, added);
+    section.lines = lines;
+}
+
+// Returns an entry line with its mnemonic replaced, keeping what follows in place
+fn rename_entry(text: &str, mnemonic: &str) -> String {
+    let start = text.len() - text.trim_start().len();
+    let Some(dot) = text.find('.') else {
+        return text.to_owned();
+    };
+    let end = start + text[start..dot].trim_end().len();
+    let mut line = text.to_owned();
+    line.replace_range(start..end, writer::mnemonic(mnemonic));
+    line
+}
+
+// Returns an entry line with the fields that differ between old and new replaced in place,
+// or written anew if the line would not be read back as new
+fn rewrite_entry(text: &str, old: &WellProp, new: &WellProp, las12: bool) -> String {
+    let mnemonic = text.split('.').next().unwrap_or("").trim();
+    let canonical = || entry_line(mnemonic, new, las12);
+    let fields = match Fields::new(text) {
+        Some(fields) if old.format == new.format && old.association == new.association => fields,
+        _ => return canonical(),
+    };
+    let (value, description) = match swapped(mnemonic, las12) {
+        true => (fields.description, fields.value),
+        false => (fields.value, fields.description),
+    };
+    // replaced from the end of the line so that earlier spans stay valid
+    let mut spans = vec![
+        (fields.unit, &old.unit, &new.unit, false),
+        (value, &old.value, &new.value, true),
+        (description, &old.description, &new.description, true),
+    ];
+    spans.sort_by_key(|(span, ..)| std::cmp::Reverse(span.start));
+    let mut line = text.to_owned();
+    for (span, old, new, padded) in spans {
+        if old != new {
+            let region = &line[span.clone()];
+            let region = if padded {
+                refill(region, new, span.end == text.len())
+            } else {
+                new.clone()
+            };
+            line.replace_range(span, &region);
+        }
+    }
+    let reread = property(0, &format!("~\n{}", line), las12, &mut vec![]);
+    if reread.values().next() == Some(new) {
+        line
+    } else {
+        canonical()
+    }
+}
+
+// Returns region with its text replaced by value, keeping the width of the region
+// where possible so that what follows stays aligned
+fn refill(region: &str, value: &str, last: bool) -> String {
+    let lead = region.len() - region.trim_start().len();
+    if region.trim().is_empty() {
+        if last {
+            return format!(" {}", value);
+        }
+        // right aligned, one space before what follows
+        let lead = region.len().saturating_sub(value.len() + 1).max(1);
+        return format!("{}{} ", " ".repeat(lead), value);
+    }
+    let trail = region.len() - region.trim_end().len();
+    let trail = match trail {
+        0 => 0,
+        _ => region.len().saturating_sub(lead + value.len()).max(1),
+    };
+    format!("{}{}{}", &region[..lead], value, " ".repeat(trail))
+}
+
+// Returns an entry line as written by the LAS 2.0 writer,
+// with value and description swapped in LAS 1.2 ~W (well) sections
+fn entry_line(mnemonic: &str, prop: &WellProp, las12: bool) -> String {
+    let (value, description) = match swapped(mnemonic, las12) {
+        true => (&prop.description, &prop.value),
+        false => (&prop.value, &prop.description),
+    };
+    let mut line = format!(
+        " {}.{}    {} : {}",
+        writer::mnemonic(mnemonic),
+        prop.unit,
+        value,
+        description
+    );
+    if let Some(format) = &prop.format {
+        line.push_str(&format!(" {{{}}}", format));
+    }
+    if let Some(association) = &prop.association {
+        line.push_str(&format!(" | {}", association));
+    }
+    line
+}
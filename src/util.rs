@@ -1,19 +1,10 @@
-use regex::Regex;
-use std::collections::HashMap;
-
-lazy_static! {
-    static ref DOT_IN_SPACES: Regex = Regex::new("\\s*[.]\\s+").unwrap();
-    pub(crate) static ref SPACES_AND_DOT: Regex = Regex::new("\\s+[.]").unwrap();
-    static ref DOT_OR_SPACES: Regex = Regex::new("[.]|\\s+").unwrap();
-    static ref LETTERS_AND_DOT_IN_SPACES: Regex = Regex::new("^\\w+\\s*[.]*s*").unwrap();
-    static ref DIGITS_AND_SPACES: Regex = Regex::new("\\d+\\s*").unwrap();
-    static ref LETTERS_IN_SPACES: Regex = Regex::new("\\s{2,}\\w*\\s{2,}").unwrap();
-    pub(crate) static ref SPACES: Regex = Regex::new("\\s+").unwrap();
-}
+use std::ops::Range;
+
+use crate::{Diagnostic, DiagnosticKind, HeaderSection, Severity, WellPropRef};
 
 /// Wellprop represents an entry in every sections
 /// excluding ~O, ~A and ~V (other, data and version sections respectively)
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct WellProp {
     /// unit of measurement
     pub unit: String,
@@ -21,6 +12,10 @@ pub struct WellProp {
     pub description: String,
     /// entry value
     pub value: String,
+    /// LAS 3.0 format of the entry, e.g. `F10.4` for `{F10.4}`
+    pub format: Option<String>,
+    /// LAS 3.0 association of the entry, e.g. `Run[1]` for `| Run[1]`
+    pub association: Option<String>,
 }
 
 impl WellProp {
@@ -43,6 +38,8 @@ impl WellProp {
             unit: unit.to_string(),
             description: description.to_string(),
             value: value.to_string(),
+            format: None,
+            association: None,
         }
     }
 }
@@ -52,7 +49,7 @@ pub(crate) fn remove_comment(raw_str: &str) -> Vec<&str> {
     raw_str
         .lines()
         .filter_map(|x| {
-            if x.trim().starts_with("#") || x.trim().len() < 1 {
+            if x.trim().starts_with('#') || x.trim().is_empty() {
                 None
             } else {
                 Some(x.trim())
@@ -61,76 +58,146 @@ pub(crate) fn remove_comment(raw_str: &str) -> Vec<&str> {
         .collect()
 }
 
-// Extracts version number and wrap mode
-// Refers to whether a wrap around mode was used in the data section. If the wrap mode is
-// false, there is no limit to the line length. If wrap mode is used, the depth value will be on its
-// own line and all lines of data will be no longer than 80 characters (including carriage return
-// and line feed).
-pub(crate) fn metadata(raw_str: &str) -> Option<(Option<f64>, bool)> {
-    lazy_static! {
-        static ref SPACEMATCH: Regex = Regex::new(r"\s+|\s*:").unwrap();
+// Returns `true` if the value and description of an entry are swapped - in LAS 1.2 ~W (well)
+// sections every entry but STRT, STOP, STEP and NULL has its description before the colon
+pub(crate) fn swapped(mnemonic: &str, las12: bool) -> bool {
+    las12 && !["STRT", "STOP", "STEP", "NULL"].contains(&mnemonic)
+}
+
+// Returns raw_str without the UTF-8 byte order mark leading it, if any
+pub(crate) fn without_bom(raw_str: &str) -> &str {
+    raw_str.strip_prefix('\u{FEFF}').unwrap_or(raw_str)
+}
+
+// Splits raw_str into its sections, returns the line number (starting from 1) of every
+// section title along with the content of the section, title line included.
+// A byte order mark leading raw_str is skipped
+pub(crate) fn sections(raw_str: &str) -> Vec<(usize, &str)> {
+    let raw_str = without_bom(raw_str);
+    let mut sections = vec![];
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for (i, line) in raw_str.split_inclusive('\n').enumerate() {
+        if line.trim_start().starts_with('~') {
+            if let Some((title_line, start)) = current {
+                sections.push((title_line, &raw_str[start..offset]));
+            }
+            current = Some((i + 1, offset));
+        }
+        offset += line.len();
     }
-    let m = raw_str
-        .split('~')
-        .nth(1)
-        .map(|x| remove_comment(x))?
-        .into_iter()
-        .skip(1)
-        .take(2)
-        .map(|x| SPACEMATCH.splitn(&x, 3).nth(1).unwrap_or("").to_string())
-        .collect::<Vec<_>>();
-    Some((m[0].parse::<f64>().ok(), m[1].to_lowercase() == "yes"))
+    if let Some((title_line, start)) = current {
+        sections.push((title_line, &raw_str[start..]));
+    }
+    sections
 }
 
-// Returns all the WellProp in a section
-// key - section signature, raw_str - string to extract them from
-pub(crate) fn property(raw_str: &str, key: &str) -> Option<HashMap<String, WellProp>> {
+// Returns all the WellProp in a section, the fields of an entry are split by `WellPropRef::parse`
+// first_line - line number of the section title
+// raw_str - content of the section, title line included
+// las12 - reads the LAS 1.2 ~W (well) layout, where the description comes before the colon
+// and the value after it, except for STRT, STOP, STEP and NULL
+// A repeated mnemonic is renamed with the number of its previous occurrences, e.g. `GR:1`.
+// Repeated mnemonics and entries missing their dot or colon are reported in diagnostics
+pub(crate) fn property(
+    first_line: usize,
+    raw_str: &str,
+    las12: bool,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> HeaderSection {
     let lines = raw_str
-        .split(key)
-        .nth(1)?
-        .split('~')
-        .nth(0)
-        .map(|x| remove_comment(x))?
-        .into_iter()
+        .lines()
+        .enumerate()
         .skip(1)
-        .collect::<Vec<_>>();
-
-    let mut prop_hash: HashMap<String, WellProp> = HashMap::new();
-
-    lines.into_iter().for_each(|line| {
-        let root = DOT_IN_SPACES.replace(line, "   none   ");
-        let title = DOT_OR_SPACES
-            .splitn(&root, 2)
-            .nth(0)
-            .unwrap_or("UNKNOWN")
-            .trim();
-        let unit = SPACES
-            .splitn(
-                LETTERS_AND_DOT_IN_SPACES
-                    .splitn(&root, 2)
-                    .nth(1)
-                    .unwrap_or(""),
-                2,
-            )
-            .nth(0)
-            .map(|x| if x.trim() == "none" { "" } else { x })
-            .unwrap_or("");
-        let description = root.split(':').nth(1).unwrap_or("").trim();
-        let description = DIGITS_AND_SPACES.replace_all(description, "");
-
-        let value = LETTERS_IN_SPACES
-            .split(root.split(":").nth(0).unwrap_or(""))
-            .collect::<Vec<_>>();
-        let value = {
-            if value.len() > 2 {
-                value[value.len() - 2].trim()
-            } else {
-                value[value.len() - 1].trim()
-            }
-        };
-        prop_hash.insert(title.to_string(), WellProp::new(unit, &description, value));
+        .map(|(i, raw)| (first_line + i, raw, raw.trim()))
+        .filter(|(_, _, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let mut prop_hash = HeaderSection::new();
+
+    lines.for_each(|(number, raw, line)| {
+        let report = |severity, kind, span| Diagnostic::new(severity, kind, number, raw, span);
+        let first_word = line.split_whitespace().next().unwrap_or(line);
+        if !line.contains('.') {
+            diagnostics.push(report(
+                Severity::Error,
+                DiagnosticKind::MissingDot,
+                first_word,
+            ));
+        } else if !line.contains(':') {
+            diagnostics.push(report(Severity::Warning, DiagnosticKind::MissingColon, ""));
+        }
+        let entry = WellPropRef::parse(line, las12);
+        let title = entry.mnemonic;
+        let prop = entry.to_owned();
+        let mnemonic = unique(title, |x| prop_hash.contains_key(x));
+        if mnemonic != title {
+            let kind = DiagnosticKind::DuplicateMnemonic {
+                mnemonic: title.to_string(),
+                renamed: mnemonic.clone(),
+            };
+            let span = raw.trim_start().get(..title.len()).unwrap_or("");
+            diagnostics.push(report(Severity::Warning, kind, span));
+        }
+        prop_hash.insert(mnemonic, prop);
     });
-    Some(prop_hash)
+    prop_hash
+}
+
+// Returns title, or title suffixed with the first free occurrence number if taken,
+// e.g. `GR:1` for the second `GR`
+pub(crate) fn unique(title: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(title) {
+        return title.to_string();
+    }
+    (1..)
+        .map(|n| format!("{}:{}", title, n))
+        .find(|x| !taken(x))
+        .unwrap_or_default()
+}
+
+// Spans of the fields of an entry line, `MNEM.UNIT  VALUE : DESCRIPTION {FORMAT} | ASSOCIATION`
+pub(crate) struct Fields {
+    pub(crate) unit: Range<usize>,
+    pub(crate) value: Range<usize>,
+    pub(crate) description: Range<usize>,
+    pub(crate) format: Option<Range<usize>>,
+    pub(crate) association: Option<Range<usize>>,
+}
+
+impl Fields {
+    // Returns `None` for a line missing its dot or colon.
+    // The value ends at the last colon before the LAS 3.0 suffixes, so that it may hold
+    // a time, e.g. `DATE.  02 JULY 2002 10:57:24 : DATE`
+    pub(crate) fn new(text: &str) -> Option<Self> {
+        let dot = text.find('.')?;
+        let unit_end = text[dot + 1..]
+            .find(|c: char| c.is_whitespace() || c == ':')
+            .map_or(text.len(), |i| dot + 1 + i);
+        let described = |end: usize| text[unit_end..end].contains(':');
+        let mut end = text.len();
+        let mut association = None;
+        if let Some(bar) = text[unit_end..].rfind('|').map(|i| unit_end + i) {
+            if described(bar) {
+                end = bar;
+                association = Some(bar + 1..text.len());
+            }
+        }
+        let mut format = None;
+        if let Some(rest) = text[..end].trim_end().strip_suffix('}') {
+            if let Some(brace) = rest.rfind('{').filter(|&b| b > unit_end && described(b)) {
+                format = Some(brace + 1..rest.len());
+                end = brace;
+            }
+        }
+        let colon = unit_end + text[unit_end..end].rfind(':')?;
+        Some(Self {
+            unit: dot + 1..unit_end,
+            value: unit_end..colon,
+            description: colon + 1..end,
+            format,
+            association,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -148,25 +215,25 @@ mod test {
         assert_eq!(expected, remove_comment(test))
     }
     #[test]
-    fn test_metatdata() {
-        let test = "~VERSION INFORMATION
-        VERS.                          2.0 :   CWLS LOG ASCII STANDARD -VERSION 2.0
-        WRAP.                          NO  :   ONE LINE PER DEPTH STEP
-        ~WELL INFORMATION";
-        assert_eq!(Some((Some(2.0), false)), metadata(test));
-        let test1 = "~VERSION INFORMATION
-        VERS.                           :   CWLS LOG ASCII STANDARD -VERSION 2.0
-        WRAP.                           :   ONE LINE PER DEPTH STEP
-        ~WELL INFORMATION";
-        assert_eq!(Some((None, false)), metadata(test1));
-        let test2 = "# LAS format log file from PETREL
-        # Project units are specified as depth units
-        #==================================================================
-        ~Version Information
-        VERS.   2.0:
-        WRAP.   NO:
-        #==================================================================";
-        assert_eq!(Some((Some(2.0), false)), metadata(test2));
+    fn test_sections() {
+        let test = "# comment
+~Version
+VERS. 2.0:
+  ~Well
+STRT .m 1.0 :
+~Ascii
+1.0";
+        let result = sections(test);
+        assert_eq!(
+            vec![
+                (2, "~Version\nVERS. 2.0:\n"),
+                (4, "  ~Well\nSTRT .m 1.0 :\n"),
+                (6, "~Ascii\n1.0")
+            ],
+            result
+        );
+        let result = sections("\u{FEFF}~Version\nVERS. 2.0:\n");
+        assert_eq!(vec![(1, "~Version\nVERS. 2.0:\n")], result);
     }
 
     #[test]
@@ -196,7 +263,8 @@ mod test {
     ~Parameter
     #==================================================================
     ~Ascii";
-        let result = property(test, "~W").unwrap();
+        let sections = sections(test);
+        let result = property(1, sections[0].1, false, &mut vec![]);
         assert_eq!(
             &WellProp::new("m", "", "1499.879000"),
             result.get("STRT").unwrap()
@@ -205,7 +273,7 @@ mod test {
             &WellProp::new("", "", "-999.250000"),
             result.get("NULL").unwrap()
         );
-        let result = property(test, "~C").unwrap();
+        let result = property(1, sections[1].1, false, &mut vec![]);
         assert_eq!(
             &WellProp::new("m", "DEPTH", ""),
             result.get("DEPT").unwrap()
@@ -213,6 +281,124 @@ mod test {
         assert_eq!(&WellProp::new("m", "", ""), result.get("Gamma").unwrap());
     }
 
+    #[test]
+    fn test_las3_property() {
+        let test = "~Log_Definition
+    DEPT .M                  : DEPTH {F}
+    NMR[1] .ms     0         : NMR Echo Array {AF10.4} | NMR_Run
+    BHT  .DEGC    35.5       : BOTTOM HOLE TEMPERATURE | Run[1]
+    ";
+        let result = property(1, test, false, &mut vec![]);
+        let mut expected = WellProp::new("M", "DEPTH", "");
+        expected.format = Some("F".to_owned());
+        assert_eq!(&expected, result.get("DEPT").unwrap());
+        let nmr = result.get("NMR[1]").unwrap();
+        assert_eq!(
+            (nmr.description.as_str(), nmr.format.as_deref()),
+            ("NMR Echo Array", Some("AF10.4"))
+        );
+        assert_eq!(nmr.association.as_deref(), Some("NMR_Run"));
+        let bht = result.get("BHT").unwrap();
+        assert_eq!((bht.value.as_str(), bht.format.as_deref()), ("35.5", None));
+        assert_eq!(bht.association.as_deref(), Some("Run[1]"));
+    }
+
+    #[test]
+    fn test_duplicate_property() {
+        let test = "~Curve
+    DEPT .M    : DEPTH
+    GR   .GAPI : GAMMA RAY
+    # comment
+    GR   .GAPI : GAMMA RAY
+    GR:1 .GAPI : GAMMA RAY
+    ";
+        let mut diagnostics = vec![];
+        let result = property(5, test, false, &mut diagnostics);
+        assert_eq!(
+            result.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["DEPT", "GR", "GR:1", "GR:1:1"]
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.line, d.column, d.width, d.kind.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    9,
+                    5,
+                    2,
+                    DiagnosticKind::DuplicateMnemonic {
+                        mnemonic: "GR".to_owned(),
+                        renamed: "GR:1".to_owned(),
+                    }
+                ),
+                (
+                    10,
+                    5,
+                    4,
+                    DiagnosticKind::DuplicateMnemonic {
+                        mnemonic: "GR:1".to_owned(),
+                        renamed: "GR:1:1".to_owned(),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_property() {
+        let test = "~Well
+    STRT .M  1670.0 : START DEPTH
+    COMP    ACME    : COMPANY
+    WELL .   A10
+    ";
+        let mut diagnostics = vec![];
+        property(1, test, false, &mut diagnostics);
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (d.severity, d.kind.clone(), d.line, d.column, d.width))
+                .collect::<Vec<_>>(),
+            vec![
+                (Severity::Error, DiagnosticKind::MissingDot, 3, 5, 4),
+                (Severity::Warning, DiagnosticKind::MissingColon, 4, 17, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_las12_property() {
+        let test = "~WELL INFORMATION BLOCK
+    STRT.M        635.0000:
+    NULL.         -999.25:
+    COMP.         COMPANY:   ANY OIL COMPANY INC.
+    SRVC. SERVICE COMPANY:   ANY LOGGING COMPANY INC.
+    UWI .  UNIQUE WELL ID:   100123401234W500
+    ";
+        let result = property(1, test, true, &mut vec![]);
+        assert_eq!(
+            &WellProp::new("M", "", "635.0000"),
+            result.get("STRT").unwrap()
+        );
+        assert_eq!(
+            &WellProp::new("", "", "-999.25"),
+            result.get("NULL").unwrap()
+        );
+        assert_eq!(
+            &WellProp::new("", "COMPANY", "ANY OIL COMPANY INC."),
+            result.get("COMP").unwrap()
+        );
+        assert_eq!(
+            &WellProp::new("", "SERVICE COMPANY", "ANY LOGGING COMPANY INC."),
+            result.get("SRVC").unwrap()
+        );
+        assert_eq!(
+            &WellProp::new("", "UNIQUE WELL ID", "100123401234W500"),
+            result.get("UWI").unwrap()
+        );
+    }
+
     #[test]
     fn test_() {
         let test = "~Well
@@ -229,7 +415,7 @@ mod test {
 	DATE    .       13-DEC-86                        :LOG DATE
 	UWI     .       100123401234W500                 :UNIQUE WELL ID
     ";
-        let result = property(test, "~W").unwrap();
+        let result = property(1, test, false, &mut vec![]);
         assert_eq!(
             &WellProp::new("", "COMPANY", "ANY OIL COMPANY INC."),
             result.get("COMP").unwrap()
@@ -0,0 +1,354 @@
+//! Checks a `Las` against the LAS 2.0 specification
+//!
+//! ## Example
+//!
+//! ```
+//! use lasrs::{validate, Las};
+//! let log = Las::new("./sample/A10.las");
+//! let report = validate::check(&log);
+//! assert!(report.is_conforming());
+//! ```
+
+use std::fmt;
+
+use crate::document::SectionKind;
+use crate::{HeaderSection, Las, LasDocument, Severity};
+
+mod house;
+
+pub use house::{HeaderRule, HouseRules, Pattern, RuleSection, Violation};
+
+// Entries required in ~V (version) section
+const VERSION_ENTRIES: [&str; 2] = ["VERS", "WRAP"];
+
+// Entries required in ~W (well) section, alternatives separated by `/`
+const WELL_ENTRIES: [&str; 12] = [
+    "STRT",
+    "STOP",
+    "STEP",
+    "NULL",
+    "COMP",
+    "WELL",
+    "FLD",
+    "LOC",
+    "PROV/CNTY/STAT/CTRY",
+    "SRVC",
+    "DATE",
+    "UWI/API",
+];
+
+// Mnemonics of the curves accepted as index, the first curve of ~C (curve) section
+const INDEX_CURVES: [&str; 4] = ["DEPT", "DEPTH", "TIME", "INDEX"];
+
+/// Represents the requirements of the LAS 2.0 specification a file can fail
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Rule {
+    /// A required section is absent, holds its signature, e.g. `~A`
+    MissingSection(String),
+    /// ~V (version) section is not the first section or ~A (data) section not the last,
+    /// holds the signature of the misplaced section
+    SectionOrder(String),
+    /// The `VERS` entry of ~V (version) section is not `2.0`, holds the value as written
+    Version(String),
+    /// A required entry is absent
+    MissingEntry {
+        /// signature of the section, e.g. `~W`
+        section: String,
+        /// mnemonic of the entry, alternatives separated by `/`, e.g. `UWI/API`
+        mnemonic: String,
+    },
+    /// `STRT`, `STOP`, `STEP` or `NULL` is not a number
+    NotANumber {
+        /// mnemonic of the entry
+        mnemonic: String,
+        /// the value as written
+        value: String,
+    },
+    /// The first curve of ~C (curve) section is not a depth, time or index curve,
+    /// holds its mnemonic
+    IndexCurve(String),
+    /// `STRT`, `STOP` or `STEP` does not match the index values of ~A (data) section
+    IndexMismatch {
+        /// mnemonic of the entry
+        mnemonic: String,
+        /// value of the entry
+        declared: f64,
+        /// first or last index value, or the first step between index values that differs
+        found: f64,
+    },
+    /// An entry breaks a house-standard `HeaderRule`
+    HouseRule {
+        /// signature of the section, e.g. `~W`
+        section: String,
+        /// mnemonic of the entry
+        mnemonic: String,
+        /// how the entry breaks the rule
+        violation: Violation,
+    },
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::MissingSection(section) => write!(f, "missing {} section", section),
+            Rule::SectionOrder(section) if section == "~V" => {
+                write!(f, "~V section must be the first section")
+            }
+            Rule::SectionOrder(section) => {
+                write!(f, "{} section must be the last section", section)
+            }
+            Rule::Version(vers) => write!(f, "version `{}` is not 2.0", vers),
+            Rule::MissingEntry { section, mnemonic } => {
+                write!(f, "missing {} entry in {} section", mnemonic, section)
+            }
+            Rule::NotANumber { mnemonic, value } => {
+                write!(f, "{} value `{}` is not a number", mnemonic, value)
+            }
+            Rule::IndexCurve(curve) => write!(
+                f,
+                "first curve `{}` is not a depth, time or index curve",
+                curve
+            ),
+            Rule::IndexMismatch {
+                mnemonic,
+                declared,
+                found,
+            } => write!(f, "{} is {} but the data has {}", mnemonic, declared, found),
+            Rule::HouseRule {
+                section,
+                mnemonic,
+                violation,
+            } => {
+                write!(f, "{} entry in {} section ", mnemonic, section)?;
+                match violation {
+                    Violation::Missing => write!(f, "is missing"),
+                    Violation::Empty => write!(f, "has no value"),
+                    Violation::NotAllowed(value) => write!(f, "value `{}` is not allowed", value),
+                    Violation::Unit(unit) => write!(f, "unit `{}` is not allowed", unit),
+                    Violation::Pattern(value) => {
+                        write!(f, "value `{}` does not match the pattern", value)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Represents a requirement a file fails
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    /// how serious the issue is
+    pub severity: Severity,
+    /// the requirement failed
+    pub rule: Rule,
+    /// line number (starting from 1) of the offending section title, `None` if not tied to a line
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}: line {}: {}", self.severity, line, self.rule),
+            None => write!(f, "{}: {}", self.severity, self.rule),
+        }
+    }
+}
+
+/// Represents the result of checking a file, the issues in the order they were found
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Report {
+    /// requirements the file fails
+    pub issues: Vec<Issue>,
+}
+
+impl Report {
+    /// Returns `true` if the file has no issue of `Severity::Error`
+    pub fn is_conforming(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Returns the issues of `Severity::Error`
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+    }
+
+    /// Returns the issues of `Severity::Warning`
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Warning)
+    }
+
+    // Adds an error not tied to a line
+    fn error(&mut self, rule: Rule) {
+        self.issues.push(Issue {
+            severity: Severity::Error,
+            rule,
+            line: None,
+        });
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the `Report` of checking a `Las` against the LAS 2.0 specification
+///
+/// Checks that ~V, ~W, ~C and ~A sections are present with ~V first and ~A last,
+/// that `VERS` is 2.0, that the entries required in ~V and ~W are present,
+/// that `STRT`, `STOP`, `STEP` and `NULL` are numbers,
+/// that the first curve is a depth, time or index curve,
+/// and that `STRT`, `STOP` and `STEP` match the index values of ~A section.
+/// A `STEP` of 0 denotes irregular sampling and is not checked
+///
+/// ## Arguments
+///
+/// `las` - the file to check
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::validate::{self, Rule};
+/// use lasrs::Las;
+/// let log = Las::new("./sample/las12.las");
+/// let report = validate::check(&log);
+/// assert!(!report.is_conforming());
+/// assert_eq!(report.issues[0].rule, Rule::Version("1.2".to_owned()));
+/// ```
+pub fn check(las: &Las) -> Report {
+    let mut report = Report::default();
+    let doc = las.document();
+    check_sections(doc, &mut report);
+
+    if let Some(version) = &doc.version {
+        if version.version() != Some(2.0) {
+            report.error(Rule::Version(version.vers.clone()));
+        }
+    }
+
+    if let Some(well) = &doc.well {
+        for mnemonic in WELL_ENTRIES.iter() {
+            if !mnemonic.split('/').any(|m| well.contains_key(m)) {
+                report.error(Rule::MissingEntry {
+                    section: "~W".to_owned(),
+                    mnemonic: mnemonic.to_string(),
+                });
+            }
+        }
+    }
+
+    let headers = doc.log().map(|l| l.headers.as_slice()).unwrap_or_default();
+    if let Some(first) = headers.first() {
+        if !INDEX_CURVES.contains(&first.to_uppercase().as_str()) {
+            report.error(Rule::IndexCurve(first.clone()));
+        }
+    }
+
+    let index = doc
+        .log()
+        .and_then(|l| l.data.as_ref())
+        .and_then(|d| d.column(0))
+        .unwrap_or_default();
+    if let Some(well) = &doc.well {
+        check_index(well, index, &mut report);
+    }
+    report
+}
+
+// Checks that ~V first and ~A last are present along with ~W and ~C,
+// and that ~V holds the `VERS` and `WRAP` entries
+fn check_sections(doc: &LasDocument, report: &mut Report) {
+    if let Some(version) = &doc.version {
+        for mnemonic in VERSION_ENTRIES.iter() {
+            if !version.entries.contains_key(mnemonic) {
+                report.error(Rule::MissingEntry {
+                    section: "~V".to_owned(),
+                    mnemonic: mnemonic.to_string(),
+                });
+            }
+        }
+    }
+    let log = doc.log();
+    let required = [
+        (doc.version.is_some(), "~V"),
+        (doc.well.is_some(), "~W"),
+        (log.is_some_and(|l| l.definitions.is_some()), "~C"),
+        (log.is_some_and(|l| l.data.is_some()), "~A"),
+    ];
+    for (present, signature) in required.iter() {
+        if !present {
+            report.error(Rule::MissingSection(signature.to_string()));
+        }
+    }
+    // sections as found in the file, none for a document built otherwise
+    let found = doc
+        .sections
+        .iter()
+        .filter(|(_, name, _)| name == "Log")
+        .collect::<Vec<_>>();
+    let misplaced = [
+        (found.first(), SectionKind::Version, "~V"),
+        (found.last(), SectionKind::Data, "~A"),
+    ];
+    for (section, kind, signature) in misplaced.iter() {
+        if let Some((line, _, found)) = section {
+            if found != kind {
+                report.issues.push(Issue {
+                    severity: Severity::Error,
+                    rule: Rule::SectionOrder(signature.to_string()),
+                    line: Some(*line),
+                });
+            }
+        }
+    }
+}
+
+// Checks that `STRT`, `STOP` and `STEP` are numbers matching the index values
+fn check_index(well: &HeaderSection, index: &[f64], report: &mut Report) {
+    let mut number = |mnemonic: &str| {
+        let prop = well.get(mnemonic)?;
+        let value = prop.value.trim().parse::<f64>();
+        if value.is_err() {
+            report.error(Rule::NotANumber {
+                mnemonic: mnemonic.to_owned(),
+                value: prop.value.clone(),
+            });
+        }
+        value.ok()
+    };
+    let (start, stop, step) = (number("STRT"), number("STOP"), number("STEP"));
+    number("NULL");
+    let (Some(first), Some(last)) = (index.first(), index.last()) else {
+        return;
+    };
+    // index values are written with fewer digits than the step may have
+    let tolerance = step.map_or(0.0, |step| step.abs() / 100.0).max(1e-6);
+    let mismatch = |mnemonic: &str, declared: f64, found: f64| Rule::IndexMismatch {
+        mnemonic: mnemonic.to_owned(),
+        declared,
+        found,
+    };
+    for (mnemonic, declared, found) in [("STRT", start, *first), ("STOP", stop, *last)] {
+        if let Some(declared) = declared.filter(|d| (d - found).abs() > tolerance) {
+            report.error(mismatch(mnemonic, declared, found));
+        }
+    }
+    if let Some(step) = step.filter(|step| *step != 0.0) {
+        let found = index
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .find(|found| (found - step).abs() > tolerance);
+        if let Some(found) = found {
+            report.error(mismatch("STEP", step, found));
+        }
+    }
+}
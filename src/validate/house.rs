@@ -0,0 +1,290 @@
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+use super::{Issue, Report, Rule};
+use crate::{HeaderSection, Las, LasError, Severity};
+
+/// Represents the header sections a `HeaderRule` applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum RuleSection {
+    /// ~W (well) section, see `Las::well_info`
+    Well,
+    /// ~C (curve) section, see `Las::curve_params`
+    Curve,
+    /// ~P (parameter) section, see `Las::log_params`
+    Parameter,
+}
+
+impl RuleSection {
+    // Returns the signature of the section, e.g. `~W`
+    fn signature(self) -> &'static str {
+        match self {
+            RuleSection::Well => "~W",
+            RuleSection::Curve => "~C",
+            RuleSection::Parameter => "~P",
+        }
+    }
+}
+
+/// Represents the ways an entry can break a `HeaderRule`
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Violation {
+    /// the entry is absent
+    Missing,
+    /// the value of the entry is empty
+    Empty,
+    /// the value is not one of the allowed values, holds the value
+    NotAllowed(String),
+    /// the unit is not the required unit, holds the unit
+    Unit(String),
+    /// the value does not match the required pattern, holds the value
+    Pattern(String),
+}
+
+/// Represents the regular expression of a `HeaderRule`, compiled once when built or loaded
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::validate::Pattern;
+/// let pattern = Pattern::new(r"^\d{16}$").unwrap();
+/// assert!(pattern.is_match("1001234012340500"));
+/// assert_eq!(pattern.as_str(), r"^\d{16}$");
+/// assert!(Pattern::new("(").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    /// Returns the compiled regular expression,
+    /// or `LasError::InvalidRules` if it is not a valid regular expression
+    ///
+    /// ## Arguments
+    ///
+    /// `pattern` - the regular expression, e.g. `^\d{16}$`
+    pub fn new(pattern: &str) -> Result<Self, LasError> {
+        Regex::new(pattern)
+            .map(Self)
+            .map_err(|err| LasError::InvalidRules(err.to_string()))
+    }
+
+    /// Returns the regular expression as written
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Returns `true` if the regular expression matches somewhere in value
+    pub fn is_match(&self, value: &str) -> bool {
+        self.0.is_match(value)
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pattern = String::deserialize(deserializer)?;
+        Pattern::new(&pattern).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Represents a house-standard requirement on an entry of ~W, ~C or ~P section
+///
+/// Every requirement left to its default is not checked, except `required`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct HeaderRule {
+    /// section holding the entry
+    pub section: RuleSection,
+    /// mnemonic of the entry, e.g. `UWI`
+    pub mnemonic: String,
+    /// severity of the issues reported for the entry, `Severity::Error` by default
+    #[cfg_attr(feature = "serde", serde(default = "error"))]
+    pub severity: Severity,
+    /// the entry must be present, `true` by default
+    #[cfg_attr(feature = "serde", serde(default = "yes"))]
+    pub required: bool,
+    /// the value must not be empty
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub non_empty: bool,
+    /// values the entry may have, any value if empty
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub allowed: Vec<String>,
+    /// unit the entry must have, e.g. `M`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unit: Option<String>,
+    /// regular expression the value must match, e.g. `^\d{16}$`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pattern: Option<Pattern>,
+}
+
+#[cfg(feature = "serde")]
+fn error() -> Severity {
+    Severity::Error
+}
+
+#[cfg(feature = "serde")]
+fn yes() -> bool {
+    true
+}
+
+impl HeaderRule {
+    /// Returns a rule requiring the entry to be present, reported as `Severity::Error`
+    ///
+    /// ## Arguments
+    ///
+    /// `section` - section holding the entry
+    ///
+    /// `mnemonic` - mnemonic of the entry
+    pub fn new(section: RuleSection, mnemonic: &str) -> Self {
+        Self {
+            section,
+            mnemonic: mnemonic.to_owned(),
+            severity: Severity::Error,
+            required: true,
+            non_empty: false,
+            allowed: vec![],
+            unit: None,
+            pattern: None,
+        }
+    }
+
+    // Returns the ways the entry of section breaks the rule
+    fn violations(&self, section: Option<&HeaderSection>) -> Vec<Violation> {
+        let prop = match section.and_then(|s| s.get(&self.mnemonic)) {
+            Some(prop) => prop,
+            None if self.required => return vec![Violation::Missing],
+            None => return vec![],
+        };
+        let value = prop.value.trim();
+        let mut violations = vec![];
+        if self.non_empty && value.is_empty() {
+            violations.push(Violation::Empty);
+        }
+        if !self.allowed.is_empty() && !self.allowed.iter().any(|a| a.trim() == value) {
+            violations.push(Violation::NotAllowed(value.to_owned()));
+        }
+        if self.unit.as_ref().is_some_and(|unit| *unit != prop.unit) {
+            violations.push(Violation::Unit(prop.unit.clone()));
+        }
+        if self.pattern.as_ref().is_some_and(|p| !p.is_match(value)) {
+            violations.push(Violation::Pattern(value.to_owned()));
+        }
+        violations
+    }
+}
+
+/// Represents a company's delivery standard - requirements on the entries of
+/// ~W (well), ~C (curve) and ~P (parameter) sections beyond the LAS 2.0 specification
+///
+/// Rule files list the rules under `rule`, loaded with `from_toml` or `from_json`
+/// when the `toml` or `json` feature is enabled
+///
+/// ```toml
+/// [[rule]]
+/// section = "well"
+/// mnemonic = "SRVC"
+/// allowed = ["ANY LOGGING COMPANY INC."]
+///
+/// [[rule]]
+/// section = "parameter"
+/// mnemonic = "RMF"
+/// severity = "warning"
+/// ```
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::validate::{HeaderRule, HouseRules, Rule, RuleSection, Violation};
+/// use lasrs::Las;
+/// let rules = HouseRules {
+///     rules: vec![
+///         HeaderRule {
+///             unit: Some("M".to_owned()),
+///             ..HeaderRule::new(RuleSection::Curve, "DEPT")
+///         },
+///         HeaderRule::new(RuleSection::Parameter, "RM"),
+///     ],
+/// };
+/// let report = rules.check(&Las::new("./sample/example.las"));
+/// assert_eq!(
+///     report.issues[0].rule,
+///     Rule::HouseRule {
+///         section: "~P".to_owned(),
+///         mnemonic: "RM".to_owned(),
+///         violation: Violation::Missing,
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub struct HouseRules {
+    /// rules in the order they are checked
+    #[cfg_attr(feature = "serde", serde(rename = "rule", alias = "rules"))]
+    pub rules: Vec<HeaderRule>,
+}
+
+impl HouseRules {
+    /// Returns the `HouseRules` of a TOML rule file,
+    /// or `LasError::InvalidRules` if the file is not a valid rule file
+    /// or a pattern is not a valid regular expression
+    ///
+    /// ## Arguments
+    ///
+    /// `raw_str` - content of the rule file
+    #[cfg(feature = "toml")]
+    pub fn from_toml(raw_str: &str) -> Result<Self, LasError> {
+        toml::from_str(raw_str).map_err(|err| LasError::InvalidRules(err.to_string()))
+    }
+
+    /// Returns the `HouseRules` of a JSON rule file,
+    /// or `LasError::InvalidRules` if the file is not a valid rule file
+    /// or a pattern is not a valid regular expression
+    ///
+    /// ## Arguments
+    ///
+    /// `raw_str` - content of the rule file
+    #[cfg(feature = "json")]
+    pub fn from_json(raw_str: &str) -> Result<Self, LasError> {
+        serde_json::from_str(raw_str).map_err(|err| LasError::InvalidRules(err.to_string()))
+    }
+
+    /// Returns the `Report` of checking a `Las` against the rules, an issue per violation
+    /// with the severity of the rule broken
+    ///
+    /// ## Arguments
+    ///
+    /// `las` - the file to check
+    pub fn check(&self, las: &Las) -> Report {
+        let doc = las.document();
+        let log = doc.log();
+        let mut report = Report::default();
+        for rule in &self.rules {
+            let section = match rule.section {
+                RuleSection::Well => doc.well.as_ref(),
+                RuleSection::Curve => log.and_then(|l| l.definitions.as_ref()),
+                RuleSection::Parameter => log.and_then(|l| l.parameters.as_ref()),
+            };
+            let issues = rule.violations(section).into_iter().map(|violation| Issue {
+                severity: rule.severity,
+                rule: Rule::HouseRule {
+                    section: rule.section.signature().to_owned(),
+                    mnemonic: rule.mnemonic.clone(),
+                    violation,
+                },
+                line: None,
+            });
+            report.issues.extend(issues);
+        }
+        report
+    }
+}
@@ -0,0 +1,79 @@
+use regex::Regex;
+
+lazy_static! {
+    static ref DATE: Regex =
+        Regex::new(r"^\d{1,4}[-/.](\d{1,2}|[A-Za-z]{3})[-/.]\d{2,4}$").unwrap();
+    static ref TIME: Regex = Regex::new(r"^\d{1,2}:\d{2}(:\d{2}(\.\d+)?)?$").unwrap();
+}
+
+/// Represents the type of the values of a curve/column in ~A (data) section
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveType {
+    /// floating point numbers
+    Float,
+    /// whole numbers
+    Integer,
+    /// text, e.g. lithology codes or sample IDs
+    String,
+    /// dates and times as written, e.g. `13-DEC-86` or `10:57:24`
+    DateTime,
+}
+
+impl CurveType {
+    /// Returns the type declared by a LAS 3.0 `{format}`, `None` if the format is unknown
+    ///
+    /// ## Arguments
+    ///
+    /// `format` - format without braces, e.g. `F10.4`, `I`, `S` or `DD/MM/YYYY`
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use lasrs::CurveType;
+    /// assert_eq!(CurveType::from_format("F10.4"), Some(CurveType::Float));
+    /// assert_eq!(CurveType::from_format("DD/MM/YYYY"), Some(CurveType::DateTime));
+    /// ```
+    pub fn from_format(format: &str) -> Option<Self> {
+        let format = format.trim().to_uppercase();
+        if ["DD", "MM", "YY", "HH", "SS"]
+            .iter()
+            .any(|part| format.contains(part))
+        {
+            return Some(CurveType::DateTime);
+        }
+        // array formats are prefixed with `A`, e.g. `AF10.4`
+        let format = match format.strip_prefix('A') {
+            Some(rest) if !rest.is_empty() => rest,
+            _ => &format,
+        };
+        match format.get(..1)? {
+            "F" | "E" => Some(CurveType::Float),
+            "I" => Some(CurveType::Integer),
+            "S" | "A" => Some(CurveType::String),
+            _ => None,
+        }
+    }
+
+    // Returns `true` if value is written as a date, a time or both
+    pub(crate) fn is_datetime(value: &str) -> bool {
+        let mut parts = value.splitn(2, ['T', ' ']);
+        match (parts.next(), parts.next()) {
+            (Some(date), Some(time)) => DATE.is_match(date) && TIME.is_match(time),
+            (Some(value), None) => DATE.is_match(value) || TIME.is_match(value),
+            _ => false,
+        }
+    }
+}
+
+/// Represents a value of a curve/column in ~A (data) section
+#[derive(Debug, Clone, PartialEq)]
+pub enum CurveValue {
+    /// value of a `CurveType::Float` curve
+    Float(f64),
+    /// value of a `CurveType::Integer` curve
+    Integer(i64),
+    /// value of a `CurveType::String` curve
+    String(String),
+    /// value of a `CurveType::DateTime` curve, as written
+    DateTime(String),
+}
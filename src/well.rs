@@ -0,0 +1,115 @@
+use crate::{HeaderSection, WellProp};
+
+// Mnemonics of the entries held by the fields of `WellHeader`
+const KNOWN: [&str; 16] = [
+    "STRT", "STOP", "STEP", "NULL", "COMP", "WELL", "FLD", "LOC", "PROV", "CNTY", "STAT", "CTRY",
+    "SRVC", "DATE", "UWI", "API",
+];
+
+/// Represents a number of ~W (well) section and its unit, e.g. `1670.0` and `M` for
+/// `STRT.M  1670.0000 : START DEPTH`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measure {
+    /// the value as a number
+    pub value: f64,
+    /// unit of measurement, empty if none
+    pub unit: String,
+}
+
+/// Represents ~W (well) section with the entries of the LAS 2.0 specification
+/// as fields - numbers parsed, text values trimmed
+///
+/// Every field is `None` if its entry is absent, numbers also if the value is not a number.
+/// Entries not held by a field are kept in `extra`
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::Las;
+/// let las = Las::new("./sample/example.las");
+/// let header = las.well_header();
+/// let strt = header.strt.unwrap();
+/// assert_eq!((strt.value, strt.unit.as_str()), (1670.0, "M"));
+/// assert_eq!(header.null, Some(-999.25));
+/// assert_eq!(header.company.as_deref(), Some("ANY OIL COMPANY INC."));
+/// assert_eq!(header.uwi.as_deref(), Some("100123401234W500"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WellHeader {
+    /// `STRT` - first index value
+    pub strt: Option<Measure>,
+    /// `STOP` - last index value
+    pub stop: Option<Measure>,
+    /// `STEP` - step between index values, 0 for irregular sampling
+    pub step: Option<Measure>,
+    /// `NULL` - value of missing values
+    pub null: Option<f64>,
+    /// `COMP` - company
+    pub company: Option<String>,
+    /// `WELL` - well name
+    pub well: Option<String>,
+    /// `FLD` - field
+    pub field: Option<String>,
+    /// `LOC` - location
+    pub location: Option<String>,
+    /// `PROV` - province
+    pub province: Option<String>,
+    /// `CNTY` - county
+    pub county: Option<String>,
+    /// `STAT` - state
+    pub state: Option<String>,
+    /// `CTRY` - country
+    pub country: Option<String>,
+    /// `SRVC` - service company
+    pub service_company: Option<String>,
+    /// `DATE` - log date
+    pub date: Option<String>,
+    /// `UWI` - unique well identifier
+    pub uwi: Option<String>,
+    /// `API` - API number
+    pub api: Option<String>,
+    /// entries not held by a field, in file order
+    pub extra: HeaderSection,
+}
+
+impl WellHeader {
+    /// Returns the `WellHeader` of the entries of ~W (well) section
+    ///
+    /// ## Arguments
+    ///
+    /// `section` - entries of ~W section, see `Las::well_info`
+    pub fn new(section: &HeaderSection) -> Self {
+        let text = |mnemonic: &str| section.get(mnemonic).map(|p| p.value.trim().to_owned());
+        let number = |prop: &WellProp| prop.value.trim().parse::<f64>().ok();
+        let measure = |mnemonic: &str| {
+            let prop = section.get(mnemonic)?;
+            Some(Measure {
+                value: number(prop)?,
+                unit: prop.unit.clone(),
+            })
+        };
+        Self {
+            strt: measure("STRT"),
+            stop: measure("STOP"),
+            step: measure("STEP"),
+            null: section.get("NULL").and_then(number),
+            company: text("COMP"),
+            well: text("WELL"),
+            field: text("FLD"),
+            location: text("LOC"),
+            province: text("PROV"),
+            county: text("CNTY"),
+            state: text("STAT"),
+            country: text("CTRY"),
+            service_company: text("SRVC"),
+            date: text("DATE"),
+            uwi: text("UWI"),
+            api: text("API"),
+            extra: section
+                .iter()
+                .filter(|(mnemonic, _)| !KNOWN.contains(&mnemonic.as_str()))
+                .map(|(mnemonic, prop)| (mnemonic.clone(), prop.clone()))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::{CurveType, Delimiter, Encoding, HeaderSection, LasDocument, WellProp};
+
+// Maximum length of a wrapped line, line feed included
+const WRAP_WIDTH: usize = 80;
+
+// Value written for missing values when ~W (well) section has no `NULL` entry
+pub(crate) const DEFAULT_NULL: f64 = -999.25;
+
+/// Options used when writing a `Las`
+///
+/// ## Example
+///
+/// ```
+/// use lasrs::WriteOptions;
+/// let options = WriteOptions {
+///     precision: Some(4),
+///     ..WriteOptions::default()
+/// };
+/// assert!(!options.wrap);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WriteOptions {
+    /// writes ~A (data) section in wrap mode - the depth on its own line,
+    /// followed by the other values on lines no longer than 80 characters
+    pub wrap: bool,
+    /// number of digits after the decimal point of data values,
+    /// the shortest representation of every value if `None`
+    pub precision: Option<usize>,
+    /// writes the file as read, comments, spacing and line endings included,
+    /// rewriting only the entries, ~O (other) text and data sections that were changed,
+    /// and adding the sections the file lacks. `precision` then only applies to changed
+    /// data sections, which keep the wrap mode of the file, `wrap` is ignored.
+    /// Not available for a file parsed with a selection of curves or an index range
+    pub lossless: bool,
+    /// encoding the file is written in, the encoding it was read with if `None`
+    pub encoding: Option<Encoding>,
+}
+
+// Values of ~A (data) section and how to write them
+pub(crate) struct Data<'a> {
+    pub(crate) headers: &'a [String],
+    // values of every column, one per row
+    pub(crate) columns: &'a [Vec<f64>],
+    pub(crate) row_count: usize,
+    // type of every column, `CurveType::Float` if absent
+    pub(crate) types: &'a [CurveType],
+    // values of string and datetime columns as written by column position
+    pub(crate) text: Option<&'a HashMap<usize, Vec<Option<String>>>>,
+    // value written for missing values, `DEFAULT_NULL` if `None`
+    pub(crate) null: Option<f64>,
+    // separator of the values of a row
+    pub(crate) dlm: Delimiter,
+}
+
+impl Data<'_> {
+    // Returns the value at row, column as written in the file
+    fn value(&self, row: usize, column: usize, options: &WriteOptions) -> String {
+        if let Some(values) = self.text.and_then(|text| text.get(&column)) {
+            if let Some(Some(value)) = values.get(row) {
+                return value.clone();
+            }
+        }
+        let value = self.columns[column][row];
+        if value.is_nan() || Some(value) == self.null {
+            return self.null.unwrap_or(DEFAULT_NULL).to_string();
+        }
+        match (self.types.get(column), options.precision) {
+            (Some(CurveType::Integer), _) => value.to_string(),
+            (_, Some(precision)) => format!("{:.*}", precision, value),
+            // whole numbers keep a decimal point to be read back as floats
+            _ if value.fract() == 0.0 && value.is_finite() => format!("{:.1}", value),
+            _ => value.to_string(),
+        }
+    }
+}
+
+// Writes ~A (data) section, every row on a line with right aligned columns,
+// or wrapped as set in options
+pub(crate) fn write_data<W: Write>(
+    writer: &mut W,
+    data: &Data,
+    options: &WriteOptions,
+) -> io::Result<()> {
+    if options.wrap {
+        writeln!(writer, "~A")?;
+    } else {
+        writeln!(writer, "~A  {}", data.headers.join(" "))?;
+    }
+    let mut widths: Vec<usize> = vec![];
+    if !options.wrap {
+        for i in 0..data.row_count {
+            for column in 0..data.columns.len() {
+                let width = data.value(i, column, options).len();
+                match widths.get_mut(column) {
+                    Some(max) => *max = (*max).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+    }
+    for i in 0..data.row_count {
+        let mut values = (0..data.columns.len()).map(|column| data.value(i, column, options));
+        if !options.wrap {
+            let line = values
+                .zip(&widths)
+                .map(|(value, width)| format!("{:>1$}", value, width))
+                .collect::<Vec<_>>();
+            let separator = match data.dlm {
+                Delimiter::Space => " ",
+                Delimiter::Comma => ", ",
+                Delimiter::Tab => "\t",
+            };
+            writeln!(writer, "{}", line.join(separator))?;
+            continue;
+        }
+        writeln!(writer, "{}", values.next().unwrap_or_default())?;
+        let mut line = String::new();
+        for value in values {
+            if !line.is_empty() && line.len() + value.len() + 2 > WRAP_WIDTH {
+                writeln!(writer, "{}", line)?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&value);
+        }
+        if !line.is_empty() {
+            writeln!(writer, "{}", line)?;
+        }
+    }
+    Ok(())
+}
+
+// Writes a LAS 2.0 file - ~V, ~W, ~C, ~P, ~O and ~A sections of the `Log` data set
+pub(crate) fn write_las<W: Write>(
+    writer: &mut W,
+    doc: &LasDocument,
+    options: &WriteOptions,
+) -> io::Result<()> {
+    let mut version = HeaderSection::new();
+    version.insert(
+        "VERS".to_owned(),
+        WellProp::new("", "CWLS LOG ASCII STANDARD - VERSION 2.0", "2.0"),
+    );
+    let wrap = if options.wrap {
+        WellProp::new("", "MULTIPLE LINES PER DEPTH STEP", "YES")
+    } else {
+        WellProp::new("", "ONE LINE PER DEPTH STEP", "NO")
+    };
+    version.insert("WRAP".to_owned(), wrap);
+    write_section(writer, "~Version Information", &version)?;
+
+    let mut well = doc.well.clone().unwrap_or_default();
+    let null = doc.null_value().unwrap_or(DEFAULT_NULL);
+    if !well.contains_key("NULL") {
+        well.insert("NULL".to_owned(), null_entry());
+    }
+    write_section(writer, "~Well Information", &well)?;
+
+    let log = doc.log();
+    let headers = log.map(|l| l.headers.as_slice()).unwrap_or_default();
+    let curves = headers
+        .iter()
+        .map(|title| {
+            let prop = log
+                .and_then(|l| l.definitions.as_ref())
+                .and_then(|d| d.get(title))
+                .cloned()
+                .unwrap_or_else(|| WellProp::new("", "", ""));
+            (title.clone(), prop)
+        })
+        .collect();
+    write_section(writer, "~Curve Information", &curves)?;
+
+    if let Some(parameters) = log.and_then(|l| l.parameters.as_ref()) {
+        write_section(writer, "~Parameter Information", parameters)?;
+    }
+    if let Some(other) = &doc.other {
+        writeln!(writer, "~Other Information")?;
+        for line in other.lines() {
+            writeln!(writer, "{}", line)?;
+        }
+    }
+    let data = log.and_then(|l| l.data.as_ref());
+    let data = Data {
+        headers,
+        columns: data.map(|d| d.columns.as_slice()).unwrap_or_default(),
+        row_count: data.map_or(0, |d| d.row_count),
+        types: data.map(|d| d.types.as_slice()).unwrap_or_default(),
+        text: data.map(|d| &d.text),
+        null: Some(null),
+        dlm: Delimiter::Space,
+    };
+    write_data(writer, &data, options)
+}
+
+// Returns the `NULL` entry of ~W (well) section added when it has none
+pub(crate) fn null_entry() -> WellProp {
+    WellProp::new("", "NULL VALUE", &DEFAULT_NULL.to_string())
+}
+
+// Writes a section of `WellProp`(s) as `MNEM.UNIT    VALUE : DESCRIPTION` lines,
+// with values right aligned
+fn write_section<W: Write>(writer: &mut W, title: &str, section: &HeaderSection) -> io::Result<()> {
+    writeln!(writer, "{}", title)?;
+    let width = |f: fn(&(&String, &WellProp)) -> usize| {
+        section.iter().map(|x| f(&x)).max().unwrap_or_default()
+    };
+    let mnemonic_width = width(|(m, p)| mnemonic(m).len() + p.unit.len());
+    let value_width = width(|(_, p)| p.value.len());
+    for (m, prop) in section {
+        let padding = mnemonic_width - mnemonic(m).len() - prop.unit.len();
+        // values are read between a run of spaces and a single space before the colon,
+        // mnemonics must touch the dot
+        writeln!(
+            writer,
+            " {}.{}{:padding$}    {:>width$} : {}",
+            mnemonic(m),
+            prop.unit,
+            "",
+            prop.value,
+            prop.description,
+            padding = padding,
+            width = value_width,
+        )?;
+    }
+    Ok(())
+}
+
+// Returns a mnemonic as written in the file, without the occurrence number of a repeated one
+pub(crate) fn mnemonic(key: &str) -> &str {
+    match key.rsplit_once(':') {
+        Some((mnemonic, n)) if n.parse::<usize>().is_ok() => mnemonic,
+        _ => key,
+    }
+}
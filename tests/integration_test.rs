@@ -1,14 +1,42 @@
-use lasrs::{Las, WellProp};
+use lasrs::{
+    CurveType, CurveValue, Delimiter, DiagnosticKind, Encoding, Las, LasError, LasReader, LasRef,
+    NullPolicy, ParseOptions, Severity, Warning, WellHeader, WellProp, WellPropRef, WriteOptions,
+};
 
 #[test]
 fn version_test() {
     let las = Las::new("./sample/example.las");
     assert_eq!(las.version(), 2.0);
+
+    // VERS and WRAP are read as entries, in any layout and order
+    let raw = "~V\n WRAP .   YES : MULTIPLE LINES\n VERS .   2.0 : V\n\
+        ~C\n DEPT.M : DEPTH\n GR.GAPI : GAMMA\n~A\n 1.0\n 10.0\n 2.0\n 20.0\n";
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(las.try_version().unwrap(), 2.0);
+    assert!(las.wrap());
+    assert_eq!(las.data(), vec![vec![1.0, 10.0], vec![2.0, 20.0]]);
+    let las: Las = "~V\nVERS.   1.2:\nWRAP.   NO:\n".parse().unwrap();
+    assert_eq!((las.version(), las.wrap()), (1.2, false));
 }
+#[test]
+fn underscore_titles_test() {
+    let raw = "~Version_Information\n VERS.   2.0 : V\n WRAP.   NO : ONE LINE\n\
+        ~Well_Information\n STRT.M   1.0 : START\n~Curve_Information\n DEPT.M : DEPTH\n\
+        GR.GAPI : GAMMA\n~Parameter_Information\n BHT.DEGC   35.5 : TEMPERATURE\n\
+        ~Other_Information\n NOTE\n~ASCII Log Data\n 1.0 10.0\n";
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(las.version(), 2.0);
+    assert_eq!(las.well_info().get("STRT").unwrap().value, "1.0");
+    assert_eq!(las.headers(), vec!["DEPT", "GR"]);
+    assert_eq!(las.log_params().get("BHT").unwrap().value, "35.5");
+    assert_eq!(las.other(), "NOTE");
+    assert_eq!(las.data(), vec![vec![1.0, 10.0]]);
+}
+
 #[test]
 fn wrap_test() {
     let las = Las::new("./sample/example.las");
-    assert_eq!(las.wrap(), false);
+    assert!(!las.wrap());
 }
 #[test]
 fn headers_test() {
@@ -29,6 +57,14 @@ fn headers_test() {
         ],
         las.headers()
     );
+
+    // titles are the mnemonics of ~C entries, a line without dot included
+    let las: Las = "~C\n DEPT.M : DEPTH\n GR GAPI : GAMMA\n~A\n 1.0 2.0\n"
+        .parse()
+        .unwrap();
+    assert_eq!(las.headers(), vec!["DEPT", "GR"]);
+    assert_eq!(las.curve("GR").unwrap().description, "GAMMA");
+    assert_eq!(las.try_column("GR").unwrap(), vec![2.0]);
 }
 
 #[test]
@@ -58,9 +94,10 @@ fn data_test() {
 fn test_column() {
     let las = Las::new("./sample/example.las");
     assert_eq!(
-        vec![1670.0, 1669.875, 1669.75, 1669.745],
+        Some(&[1670.0, 1669.875, 1669.75, 1669.745][..]),
         las.column("DEPT")
     );
+    assert_eq!(las.column("GR"), None);
 }
 
 #[test]
@@ -85,11 +122,10 @@ fn test_header_and_desc() {
     .into_iter()
     .map(|a| (a.0.to_string(), a.1.to_string()))
     .collect::<Vec<_>>();
-    assert_eq!(
-        expected.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap()),
-        las.headers_and_desc()
-            .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
-    );
+    let mut result = las.headers_and_desc();
+    expected.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    result.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    assert_eq!(expected, result);
 }
 
 #[test]
@@ -128,47 +164,47 @@ fn well_section_test() {
     let las = Las::new("./sample/example.las");
     let well_section = las.well_info();
     assert_eq!(
-    &WellProp::new("M", "START DEPTH", "1670.0000"),
+        &WellProp::new("M", "START DEPTH", "1670.0000"),
         well_section.get("STRT").unwrap()
     );
     assert_eq!(
-    &WellProp::new("M", "STOP DEPTH", "1669.7500"),
+        &WellProp::new("M", "STOP DEPTH", "1669.7500"),
         well_section.get("STOP").unwrap()
     );
     assert_eq!(
-    &WellProp::new("M", "STEP", "-0.1250"),
+        &WellProp::new("M", "STEP", "-0.1250"),
         well_section.get("STEP").unwrap()
     );
     assert_eq!(
-    &WellProp::new("", "NULL VALUE", "-999.25"),
+        &WellProp::new("", "NULL VALUE", "-999.25"),
         well_section.get("NULL").unwrap()
     );
     assert_eq!(
-    &WellProp::new("", "COMPANY", "ANY OIL COMPANY INC."),
+        &WellProp::new("", "COMPANY", "ANY OIL COMPANY INC."),
         well_section.get("COMP").unwrap()
     );
     assert_eq!(
-    &WellProp::new("", "WELL", "ANY ET AL 12-34-12-34"),
+        &WellProp::new("", "WELL", "ANY ET AL 12-34-12-34"),
         well_section.get("WELL").unwrap()
     );
     assert_eq!(
-    &WellProp::new("", "FIELD", "WILDCAT"),
+        &WellProp::new("", "FIELD", "WILDCAT"),
         well_section.get("FLD").unwrap()
     );
     assert_eq!(
-    &WellProp::new("", "LOCATION", "12-34-12-34W5M"),
+        &WellProp::new("", "LOCATION", "12-34-12-34W5M"),
         well_section.get("LOC").unwrap()
     );
     assert_eq!(
-    &WellProp::new("", "PROVINCE", "ALBERTA"),
+        &WellProp::new("", "PROVINCE", "ALBERTA"),
         well_section.get("PROV").unwrap()
     );
     assert_eq!(
-    &WellProp::new("", "SERVICE COMPANY", "ANY LOGGING COMPANY INC."),
+        &WellProp::new("", "SERVICE COMPANY", "ANY LOGGING COMPANY INC."),
         well_section.get("SRVC").unwrap()
     );
     assert_eq!(
-    &WellProp::new("", "LOG DATE", "13-DEC-86"),
+        &WellProp::new("", "LOG DATE", "13-DEC-86"),
         well_section.get("DATE").unwrap()
     );
     assert_eq!(
@@ -176,3 +212,1693 @@ fn well_section_test() {
         well_section.get("UWI").unwrap()
     );
 }
+
+#[test]
+fn open_test() {
+    assert!(Las::open("./sample/example.las").is_ok());
+    assert!(matches!(
+        Las::open("./sample/missing.las"),
+        Err(LasError::Io(_))
+    ));
+}
+
+#[test]
+fn try_version_test() {
+    let las = Las::new("./sample/example.las");
+    assert_eq!(las.try_version().unwrap(), 2.0);
+    let las = Las::new("./sample/malformed.las");
+    match las.try_version() {
+        Err(LasError::InvalidVersion(raw)) => assert_eq!(raw, "X.Y"),
+        other => panic!("expected InvalidVersion, got {:?}", other),
+    }
+}
+
+#[test]
+fn try_data_test() {
+    let las = Las::new("./sample/A10.las");
+    assert_eq!(las.try_data().unwrap(), las.data());
+    let las = Las::new("./sample/malformed.las");
+    match las.try_data() {
+        Err(LasError::MalformedRow {
+            line,
+            expected,
+            found,
+        }) => assert_eq!((line, expected, found), (15, 3, 2)),
+        other => panic!("expected MalformedRow, got {:?}", other),
+    }
+}
+
+#[test]
+fn try_column_test() {
+    let las = Las::new("./sample/example.las");
+    assert_eq!(
+        las.try_column("DEPT").unwrap(),
+        vec![1670.0, 1669.875, 1669.75, 1669.745]
+    );
+    assert!(matches!(
+        las.try_column("GR"),
+        Err(LasError::UnknownCurve(_))
+    ));
+}
+
+#[test]
+fn missing_section_test() {
+    let las = Las::new("./sample/malformed.las");
+    assert!(matches!(
+        las.try_log_params(),
+        Err(LasError::MissingSection(_))
+    ));
+    assert!(las.try_well_info().is_ok());
+}
+
+#[test]
+fn document_test() {
+    let las = Las::new("./sample/A10.las");
+    let doc = las.document();
+    assert_eq!(doc.version.as_ref().and_then(|v| v.version()), Some(2.0));
+    assert_eq!(doc.well.as_ref().unwrap(), &las.well_info());
+    assert_eq!(doc.other, None);
+    let log = doc.log().unwrap();
+    assert_eq!(log.headers, las.headers());
+    assert_eq!(log.definitions.as_ref().unwrap(), &las.curve_params());
+    assert_eq!(log.parameters.as_ref().unwrap().len(), 0);
+    assert_eq!(log.data.as_ref().unwrap().row_count(), las.row_count());
+    assert_eq!(las.row_count(), 1835);
+}
+
+#[test]
+fn constructors_test() {
+    use std::fs;
+    let expected = Las::new("./sample/example.las");
+    let las = Las::from_reader(fs::File::open("./sample/example.las").unwrap()).unwrap();
+    assert_eq!(las.document(), expected.document());
+    let raw = fs::read_to_string("./sample/example.las").unwrap();
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(las.document(), expected.document());
+    let las = Las::from_bytes(raw.as_bytes()).unwrap();
+    assert_eq!(las.document(), expected.document());
+    let las = Las::from_bytes(&[0x7e, 0x56, 0xff, 0xfe]).unwrap();
+    assert_eq!(las.encoding(), Encoding::Latin1);
+    assert_eq!(las.blob, "~V\u{ff}\u{fe}");
+    let options = ParseOptions {
+        encoding: Some(Encoding::Utf8),
+        ..ParseOptions::default()
+    };
+    assert!(matches!(
+        Las::from_reader_with(&[0x7e, 0x56, 0xff, 0xfe][..], &options),
+        Err(LasError::Io(_))
+    ));
+}
+
+#[test]
+fn wrap_data_test() {
+    let las = Las::new("./sample/wrapped.las");
+    assert!(las.wrap());
+    assert_eq!(las.column_count(), 36);
+    let data = las.try_data().unwrap();
+    assert_eq!(data.len(), 5);
+    assert!(data.iter().all(|row| row.len() == 36));
+    assert_eq!(
+        las.try_column("DEPT").unwrap(),
+        vec![910.0, 909.875, 909.75, 909.625, 909.5]
+    );
+    assert_eq!(&data[1][34..], &[0.0, 0.0]);
+
+    // a lone value completing a depth step is not mistaken for the next depth
+    let raw = "~V\nVERS. 2.0 :\nWRAP. YES :\n~C\nDEPT.M :\nA.M :\nB.M :\nC.M :\n~A\n\
+               1.0\n10.0 20.0\n30.0\n2.0\n11.0 21.0\n31.0\n";
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(
+        las.try_data().unwrap(),
+        vec![vec![1.0, 10.0, 20.0, 30.0], vec![2.0, 11.0, 21.0, 31.0]]
+    );
+
+    // lines are read until the row holds a value per curve, whatever their length
+    let raw = "~V\nVERS. 2.0 :\nWRAP. YES :\n~C\nDEPT.M :\nA.M :\nB.M :\nC.M :\n~A\n\
+               1.0\n10.0\n20.0 30.0\n2.0\n11.0\n21.0 31.0";
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(
+        las.try_data().unwrap(),
+        vec![vec![1.0, 10.0, 20.0, 30.0], vec![2.0, 11.0, 21.0, 31.0]]
+    );
+    let rows = LasReader::new(raw.as_bytes()).unwrap();
+    assert_eq!(rows.collect::<Result<Vec<_>, _>>().unwrap(), las.data());
+
+    let raw = "~V\nVERS. 2.0 :\nWRAP. YES :\n~C\nDEPT.M :\nA.M :\nB.M :\nC.M :\n~A\n\
+               1.0\n10.0\n2.0\n11.0 21.0 31.0\n";
+    let las: Las = raw.parse().unwrap();
+    match las.try_data() {
+        Err(LasError::MalformedRow {
+            line,
+            expected,
+            found,
+        }) => assert_eq!((line, expected, found), (10, 4, 6)),
+        other => panic!("expected MalformedRow, got {:?}", other),
+    }
+}
+
+#[test]
+fn write_wrapped_data_test() {
+    let las = Las::new("./sample/wrapped.las");
+    let mut out = vec![];
+    let options = WriteOptions {
+        wrap: true,
+        ..WriteOptions::default()
+    };
+    las.write_data(&mut out, &options).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.lines().all(|line| line.len() < 80));
+    assert_eq!(out.lines().nth(1), Some("910.0"));
+
+    let header = &las.blob[..las.blob.find("~A").unwrap()];
+    let las_out: Las = format!("{}{}", header, out).parse().unwrap();
+    assert_eq!(las_out.try_data().unwrap(), las.data());
+
+    let mut out = vec![];
+    las.write_data(&mut out, &WriteOptions::default()).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.lines().count(), 6);
+}
+
+#[test]
+fn las3_test() {
+    let las = Las::new("./sample/las3.las");
+    assert_eq!(las.version(), 3.0);
+    assert_eq!(
+        las.document().version.as_ref().unwrap().dlm,
+        Delimiter::Comma
+    );
+    assert_eq!(
+        las.headers(),
+        vec!["DEPT", "DT", "RHOB", "NMR[1]", "NMR[2]"]
+    );
+    assert_eq!(
+        las.try_data().unwrap()[1],
+        vec![1669.875, 123.45, 2550.0, 0.13, 0.24]
+    );
+    let dept = las.curve_params().remove("DEPT").unwrap();
+    assert_eq!(dept.description, "DEPTH");
+    assert_eq!(dept.format.as_deref(), Some("F"));
+    let bht = las.log_params().remove("BHT").unwrap();
+    assert_eq!(bht.value, "35.5");
+    assert_eq!(bht.association.as_deref(), Some("Run[1]"));
+
+    let names = las
+        .data_sets()
+        .iter()
+        .map(|set| set.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["Log", "Core", "Inclinometry"]);
+    let log = las.data_set("Log").unwrap();
+    assert_eq!(log.array("NMR"), vec![3, 4]);
+    let core = las.data_set("Core").unwrap();
+    assert_eq!(
+        core.data.as_ref().unwrap().rows().collect::<Vec<_>>(),
+        vec![vec![1669.9, 1670.2, 0.3], vec![1669.7, 1669.8, 0.1]]
+    );
+    assert!(core.parameters.as_ref().unwrap().contains_key("C_SRS"));
+    let inclinometry = las.data_set("inclinometry").unwrap();
+    assert_eq!(inclinometry.headers, vec!["MD", "TVD", "AZIM", "DIP"]);
+    assert_eq!(inclinometry.data.as_ref().unwrap().row_count(), 3);
+}
+
+#[test]
+fn delimiter_test() {
+    let raw = "~V\nVERS. 3.0 :\nWRAP. NO :\nDLM . TAB :\n~C\nDEPT.M :\nGR.GAPI :\n~A\n\
+               100.0\t45.5\n100.5\t46.0\n";
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(
+        las.try_data().unwrap(),
+        vec![vec![100.0, 45.5], vec![100.5, 46.0]]
+    );
+}
+
+#[test]
+fn las12_well_section_test() {
+    let las = Las::new("./sample/las12.las");
+    assert_eq!(las.version(), 1.2);
+    let well_section = las.well_info();
+    assert_eq!(
+        &WellProp::new("M", "", "635.0000"),
+        well_section.get("STRT").unwrap()
+    );
+    assert_eq!(
+        &WellProp::new("", "", "-999.25"),
+        well_section.get("NULL").unwrap()
+    );
+    assert_eq!(
+        &WellProp::new("", "COMPANY", "ANY OIL COMPANY INC."),
+        well_section.get("COMP").unwrap()
+    );
+    assert_eq!(
+        &WellProp::new("", "WELL", "AAAAA_2"),
+        well_section.get("WELL").unwrap()
+    );
+    assert_eq!(
+        &WellProp::new("", "LOG DATE", "13-DEC-86"),
+        well_section.get("DATE").unwrap()
+    );
+    assert_eq!(
+        &WellProp::new("", "UNIQUE WELL ID", "100123401234W500"),
+        well_section.get("UWI").unwrap()
+    );
+    // other sections keep the 2.0 layout
+    assert_eq!(
+        &WellProp::new("DEGC", "BOTTOM HOLE TEMPERATURE", "35.5000"),
+        las.log_params().get("BHT").unwrap()
+    );
+    assert_eq!(las.row_count(), 3);
+}
+
+#[test]
+fn null_value_test() {
+    let raw = "~V\nVERS. 2.0 :\nWRAP. NO :\n~W\nNULL. -999.25 :\n~C\nDEPT.M :\nGR.GAPI :\n~A\n\
+               100.0  45.5\n100.5  -999.25\n101.0  4x.0\n";
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(las.null_value(), Some(-999.25));
+    assert_eq!(
+        las.data(),
+        vec![
+            vec![100.0, 45.5],
+            vec![100.5, -999.25],
+            vec![101.0, -999.25]
+        ]
+    );
+    assert_eq!(
+        las.nullable_data(),
+        vec![
+            vec![Some(100.0), Some(45.5)],
+            vec![Some(100.5), None],
+            vec![Some(101.0), None]
+        ]
+    );
+
+    let options = ParseOptions {
+        null: NullPolicy::Nan,
+        ..ParseOptions::default()
+    };
+    let las = Las::from_reader_with(raw.as_bytes(), &options).unwrap();
+    let data = las.data();
+    assert!(data[1][1].is_nan() && data[2][1].is_nan());
+    assert_eq!(las.nullable_data()[2], vec![Some(101.0), None]);
+
+    let options = ParseOptions {
+        strict: true,
+        ..ParseOptions::default()
+    };
+    match Las::from_reader_with(raw.as_bytes(), &options) {
+        Err(LasError::InvalidValue {
+            line,
+            column,
+            value,
+        }) => assert_eq!((line, column, value.as_str()), (12, 8, "4x.0")),
+        other => panic!("expected InvalidValue, got {:?}", other.err()),
+    }
+    assert!(Las::open_with("./sample/A10.las", &options).is_ok());
+}
+
+#[test]
+fn typed_curves_test() {
+    let las = Las::new("./sample/typed.las");
+    assert_eq!(
+        las.headers(),
+        vec!["DEPT", "GR", "LITH", "SAMP", "DATE", "TIME"]
+    );
+    assert_eq!(
+        las.typed_column("GR").unwrap(),
+        vec![
+            Some(CurveValue::Float(45.2)),
+            None,
+            Some(CurveValue::Float(60.1))
+        ]
+    );
+    assert_eq!(
+        las.typed_column("SAMP").unwrap()[1],
+        Some(CurveValue::Integer(102))
+    );
+    assert_eq!(
+        las.typed_column("DATE").unwrap()[2],
+        Some(CurveValue::DateTime("14-DEC-86".to_owned()))
+    );
+    assert_eq!(
+        las.typed_column("TIME").unwrap()[0],
+        Some(CurveValue::DateTime("10:57:24".to_owned()))
+    );
+    // the f64 view keeps string values as missing
+    assert_eq!(las.data()[0][2], -999.25);
+    assert_eq!(las.data()[0][3], 101.0);
+    assert!(matches!(
+        las.typed_column("PHI"),
+        Err(LasError::UnknownCurve(_))
+    ));
+
+    // declared types take precedence over inferred ones
+    let mut options = ParseOptions::default();
+    options.types.insert("SAMP".to_owned(), CurveType::String);
+    let las = Las::open_with("./sample/typed.las", &options).unwrap();
+    assert_eq!(
+        las.typed_column("SAMP").unwrap()[0],
+        Some(CurveValue::String("101".to_owned()))
+    );
+
+    // strict mode only accepts text in declared string and datetime curves
+    let mut options = ParseOptions {
+        strict: true,
+        ..ParseOptions::default()
+    };
+    assert!(matches!(
+        Las::open_with("./sample/typed.las", &options),
+        Err(LasError::InvalidValue {
+            line: 22,
+            column: 23,
+            ..
+        })
+    ));
+    for (title, kind) in [
+        ("LITH", CurveType::String),
+        ("DATE", CurveType::DateTime),
+        ("TIME", CurveType::DateTime),
+    ] {
+        options.types.insert(title.to_owned(), kind);
+    }
+    let las = Las::open_with("./sample/typed.las", &options).unwrap();
+    assert_eq!(las.curve_types()[3], CurveType::Integer);
+    assert_eq!(
+        las.typed_column("LITH").unwrap()[1],
+        Some(CurveValue::String("SHALE".to_owned()))
+    );
+
+    // LAS 3.0 `{format}` declarations
+    let las = Las::new("./sample/las3.las");
+    assert!(las.curve_types().iter().all(|x| *x == CurveType::Float));
+}
+
+#[test]
+fn header_order_test() {
+    let las = Las::new("./sample/A10.las");
+    let curves = las.curve_params();
+    assert_eq!(curves.keys().cloned().collect::<Vec<_>>(), las.headers());
+    assert_eq!(
+        las.headers_and_desc()
+            .into_iter()
+            .map(|(title, _)| title)
+            .collect::<Vec<_>>(),
+        las.headers()
+    );
+    let las = Las::new("./sample/example.las");
+    let well = las.well_info();
+    let mnemonics = well.keys().map(String::as_str).collect::<Vec<_>>();
+    assert_eq!(
+        mnemonics,
+        vec![
+            "STRT", "STOP", "STEP", "NULL", "COMP", "WELL", "FLD", "LOC", "PROV", "SRVC", "DATE",
+            "UWI"
+        ]
+    );
+    assert_eq!(well.position("NULL"), Some(3));
+    let (mnemonic, prop) = well.iter().nth(4).unwrap();
+    assert_eq!(
+        (mnemonic.as_str(), prop.value.as_str()),
+        ("COMP", "ANY OIL COMPANY INC.")
+    );
+}
+
+#[test]
+fn duplicate_mnemonics_test() {
+    let las = Las::new("./sample/duplicates.las");
+    assert_eq!(las.headers(), vec!["DEPT", "GR", "RHOB", "GR:1", "GR:2"]);
+    assert_eq!(
+        las.curve_params().keys().cloned().collect::<Vec<_>>(),
+        las.headers()
+    );
+    assert_eq!(
+        las.curve_params().get("GR:1").unwrap().description,
+        "GAMMA RAY REPEAT"
+    );
+    assert_eq!(las.try_column("GR").unwrap(), vec![45.2, 51.3, 60.1]);
+    assert_eq!(las.try_column("GR:2").unwrap(), vec![46.3, 50.9, 59.8]);
+    assert_eq!(
+        las.well_info().get("WELL:1").unwrap().value,
+        "COMPOSITE WELL 2"
+    );
+    let renamed = las
+        .warnings()
+        .iter()
+        .map(|w| match w {
+            Warning::DuplicateMnemonic { line, renamed, .. } => (*line, renamed.as_str()),
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(renamed, vec![(10, "WELL:1"), (17, "GR:1"), (18, "GR:2")]);
+    assert_eq!(
+        las.warnings()[2].to_string(),
+        "line 18: duplicate mnemonic `GR` renamed to `GR:2`"
+    );
+    assert!(Las::new("./sample/example.las").warnings().is_empty());
+}
+
+#[test]
+fn write_las_test() {
+    use std::fs;
+    for file in ["example", "A10", "typed", "duplicates", "las12", "wrapped"] {
+        let las = Las::new(format!("./sample/{}.las", file));
+        let mut out = vec![];
+        las.write_to(&mut out, &WriteOptions::default()).unwrap();
+        let copy = Las::from_bytes(&out).unwrap();
+        assert_eq!(copy.version(), 2.0);
+        assert_eq!(copy.well_info(), las.well_info());
+        assert_eq!(copy.curve_params(), las.curve_params());
+        assert_eq!(copy.log_params(), las.log_params());
+        assert_eq!(copy.other(), las.other());
+        assert_eq!(copy.curve_types(), las.curve_types());
+        assert_eq!(
+            format!("{:?}", copy.nullable_data()),
+            format!("{:?}", las.nullable_data())
+        );
+    }
+
+    let las = Las::new("./sample/typed.las");
+    let options = WriteOptions {
+        precision: Some(2),
+        ..WriteOptions::default()
+    };
+    las.save_with("typed_copy.las", &options).unwrap();
+    let out = fs::read_to_string("typed_copy.las").unwrap();
+    fs::remove_file("typed_copy.las").expect("Could not clean up after test");
+    assert!(out.contains("\n NULL.               -999.25 : NULL VALUE\n"));
+    assert!(out.ends_with("1501.00   60.10 -999.25 103 14-DEC-86 09:12:45\n"));
+
+    let las = Las::new("./sample/duplicates.las");
+    let mut out = vec![];
+    las.write_to(&mut out, &WriteOptions::default()).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.matches("\n GR.GAPI").count(), 3);
+
+    // without a `NULL` entry missing values are written as -999.25, declared in ~W section
+    let raw =
+        "~V\n VERS. 2.0 :\n WRAP. NO :\n~W\n STRT.M  1.0 : START\n~C\n DEPT.M :\n GR.GAPI :\n\
+               ~A\n1.0 10.0\n2.0 NaN\n";
+    let las: Las = raw.parse().unwrap();
+    let mut out = vec![];
+    las.write_data(&mut out, &WriteOptions::default()).unwrap();
+    assert!(String::from_utf8(out).unwrap().ends_with("2.0 -999.25\n"));
+    let mut out = vec![];
+    las.write_to(&mut out, &WriteOptions::default()).unwrap();
+    assert_eq!(Las::from_bytes(&out).unwrap().null_value(), Some(-999.25));
+
+    let mut las = las;
+    let data = las.document_mut().data_sets[0].data.as_mut().unwrap();
+    data.column_mut(1).unwrap()[0] = f64::NAN;
+    let options = WriteOptions {
+        lossless: true,
+        ..WriteOptions::default()
+    };
+    let mut out = vec![];
+    las.write_to(&mut out, &options).unwrap();
+    let copy = Las::from_bytes(&out).unwrap();
+    assert_eq!(copy.null_value(), Some(-999.25));
+    assert_eq!(
+        copy.nullable_data(),
+        vec![vec![Some(1.0), None], vec![Some(2.0), None]]
+    );
+}
+
+#[test]
+fn lossless_write_test() {
+    let options = WriteOptions {
+        lossless: true,
+        ..WriteOptions::default()
+    };
+    let changed = |las: &Las, original: &str| {
+        let mut out = vec![];
+        las.write_to(&mut out, &options).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = original
+            .split_inclusive('\n')
+            .zip(out.split_inclusive('\n'))
+            .filter(|(a, b)| a != b)
+            .map(|(a, b)| (a.to_owned(), b.to_owned()))
+            .collect::<Vec<_>>();
+        (out, lines)
+    };
+
+    let mut las = Las::new("./sample/A10.las");
+    let blob = las.blob.clone();
+    assert_eq!(changed(&las, &blob).0, blob);
+
+    let well = las.document_mut().well.as_mut().unwrap();
+    well.get_mut("STRT").unwrap().value = "1500.000000".to_owned();
+    well.get_mut("COMP").unwrap().value = "ANY OIL COMPANY INC.".to_owned();
+    let (out, lines) = changed(&las, &blob);
+    assert_eq!(
+        lines,
+        vec![
+            (
+                "STRT .m       1499.879000 :\r\n".to_owned(),
+                "STRT .m       1500.000000 :\r\n".to_owned()
+            ),
+            (
+                "COMP.           : COMPANY\r\n".to_owned(),
+                "COMP. ANY OIL COMPANY INC. : COMPANY\r\n".to_owned()
+            ),
+        ]
+    );
+    assert!(out.starts_with("# LAS format log file from PETREL\r\n"));
+    let copy: Las = out.parse().unwrap();
+    assert_eq!(copy.well_info(), las.well_info());
+    assert_eq!(copy.data(), las.data());
+
+    // entries removed and added, ~O text and data changed
+    let mut las = Las::new("./sample/example.las");
+    let blob = las.blob.clone();
+    let doc = las.document_mut();
+    doc.well.as_mut().unwrap().remove("UWI");
+    doc.other = Some("Edited".to_owned());
+    let log = doc.data_sets.iter_mut().find(|s| s.name == "Log").unwrap();
+    let params = log.parameters.as_mut().unwrap();
+    params.get_mut("BHT").unwrap().description = "BOTTOM HOLE TEMP".to_owned();
+    params.insert(
+        "RM".to_owned(),
+        WellProp::new("OHMM", "MUD RESISTIVITY", "0.35"),
+    );
+    log.data.as_mut().unwrap().column_mut(1).unwrap()[0] = 99.5;
+    let (out, _) = changed(&las, &blob);
+    assert!(!out.contains("UWI"));
+    assert!(out.contains(" BHT    .DEGC           35.5000         :   BOTTOM HOLE TEMP\n"));
+    assert!(out.contains(" DFD    .K/M3           1525.0000       :   DRILL FLUID DENSITY\n RM.OHMM    0.35 : MUD RESISTIVITY\n~OTHER\nEdited\n~A"));
+    let copy: Las = out.parse().unwrap();
+    assert_eq!(copy.well_info(), las.well_info());
+    assert_eq!(copy.log_params(), las.log_params());
+    assert_eq!(copy.other(), "Edited");
+    assert_eq!(copy.data(), las.data());
+
+    // LAS 1.2 values are written after the colon
+    let mut las = Las::new("./sample/las12.las");
+    let blob = las.blob.clone();
+    let well = las.document_mut().well.as_mut().unwrap();
+    well.get_mut("COMP").unwrap().value = "ACME".to_owned();
+    let (out, lines) = changed(&las, &blob);
+    assert_eq!(lines.len(), 1);
+    let copy: Las = out.parse().unwrap();
+    assert_eq!(copy.well_info(), las.well_info());
+
+    // sections missing from the file are added before ~A section
+    let raw = "~V\n VERS.   2.0 : V\n WRAP.   NO : ONE LINE\n~C\n DEPT.M : DEPTH\n~A\n 1.0";
+    let mut las: Las = raw.parse().unwrap();
+    las.set_other("Edited");
+    let prop = WellProp::new("DEGC", "BOTTOM HOLE TEMPERATURE", "35.5");
+    las.set_param("BHT", prop).unwrap();
+    let (out, _) = changed(&las, raw);
+    assert!(out.starts_with(raw.split("~A").next().unwrap()));
+    assert!(out.contains("~Parameter Information\n BHT.DEGC    35.5 : BOTTOM HOLE TEMPERATURE\n"));
+    assert!(out.ends_with("~Other Information\nEdited\n~A\n 1.0"));
+    let copy: Las = out.parse().unwrap();
+    assert_eq!(copy.log_params(), las.log_params());
+    assert_eq!(copy.other(), "Edited");
+    let mut las: Las = "~V\n VERS.   2.0 : V".parse().unwrap();
+    las.set_other("Edited");
+    let (out, _) = changed(&las, "");
+    assert_eq!(out, "~V\n VERS.   2.0 : V\n~Other Information\nEdited\n");
+
+    // changed data sections keep the wrap mode of the file
+    let mut las = Las::new("./sample/example.las");
+    las.drop_curve("DT").unwrap();
+    let wrapped = WriteOptions {
+        lossless: true,
+        wrap: true,
+        ..WriteOptions::default()
+    };
+    let mut out = vec![];
+    las.write_to(&mut out, &wrapped).unwrap();
+    let copy = Las::from_bytes(&out).unwrap();
+    assert!(!copy.wrap());
+    assert_eq!(copy.try_data().unwrap(), las.data());
+}
+
+#[test]
+fn editing_test() {
+    let mut las = Las::new("./sample/example.las");
+    let prop = WellProp::new("", "OPERATOR", "ACME");
+    assert_eq!(las.set_well_prop("OPER", prop.clone()).unwrap(), None);
+    assert_eq!(las.well_info().keys().last().unwrap(), "OPER");
+    assert!(las.remove_well_prop("UWI").is_some());
+    assert!(las.remove_well_prop("UWI").is_none());
+    assert!(matches!(
+        las.set_well_prop("BAD NAME", prop.clone()),
+        Err(LasError::InvalidMnemonic(_))
+    ));
+    assert!(las.remove_param("MUD").is_some());
+    las.set_param("RM", WellProp::new("OHMM", "MUD RESISTIVITY", "0.35"))
+        .unwrap();
+    let old = las
+        .set_curve_prop("RHOB", WellProp::new("G/C3", "BULK DENSITY", ""))
+        .unwrap();
+    assert_eq!(old.unwrap().unit, "K/M3");
+    assert!(matches!(
+        las.set_curve_prop("GR", prop.clone()),
+        Err(LasError::UnknownCurve(_))
+    ));
+
+    las.rename_curve("ILD", "RESD").unwrap();
+    assert!(matches!(
+        las.rename_curve("RESD", "ILM"),
+        Err(LasError::DuplicateCurve(_))
+    ));
+    assert!(matches!(
+        las.rename_curve("GR", "GAMMA"),
+        Err(LasError::UnknownCurve(_))
+    ));
+    assert_eq!(
+        las.drop_curve("DT").unwrap().unwrap().description,
+        "SONIC TRANSIT TIME"
+    );
+    assert!(matches!(
+        las.drop_curve("DT"),
+        Err(LasError::UnknownCurve(_))
+    ));
+    assert!(matches!(
+        las.add_curve("VSH", WellProp::new("V/V", "SHALE VOLUME", ""), vec![0.5]),
+        Err(LasError::LengthMismatch {
+            expected: 4,
+            found: 1
+        })
+    ));
+    las.add_curve(
+        "VSH",
+        WellProp::new("V/V", "SHALE VOLUME", ""),
+        vec![0.1, 0.2, 0.3, 0.4],
+    )
+    .unwrap();
+    las.set_other("Headers fixed");
+    assert_eq!(
+        las.headers(),
+        vec!["DEPT", "RHOB", "NPHI", "SFLU", "SFLA", "ILM", "RESD", "VSH"]
+    );
+    assert_eq!(
+        las.data()[0],
+        vec![1670.0, 2550.0, 0.45, 123.45, 123.45, 110.2, 105.6, 0.1]
+    );
+
+    for lossless in [false, true] {
+        let options = WriteOptions {
+            lossless,
+            ..WriteOptions::default()
+        };
+        let mut out = vec![];
+        las.write_to(&mut out, &options).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        if lossless {
+            assert!(out.contains(
+                " RESD    .OHMM           07 120 46 00             :  8  DEEP RESISTIVITY\n \
+                 VSH.V/V     : SHALE VOLUME\n"
+            ));
+        }
+        let copy: Las = out.parse().unwrap();
+        assert_eq!(copy.well_info(), las.well_info());
+        assert_eq!(copy.curve_params(), las.curve_params());
+        assert_eq!(copy.log_params(), las.log_params());
+        assert_eq!(copy.other(), "Headers fixed");
+        assert_eq!(copy.data(), las.data());
+    }
+
+    // a curve of ~A section without an entry in ~C section
+    let mut bare = Las::new("./sample/example.las");
+    let log = &mut bare.document_mut().data_sets[0];
+    log.definitions.as_mut().unwrap().remove("NPHI");
+    let prop = WellProp::new("V/V", "NEUTRON POROSITY", "");
+    assert_eq!(bare.set_curve_prop("NPHI", prop.clone()).unwrap(), None);
+    assert_eq!(bare.curve_params().get("NPHI"), Some(&prop));
+    let log = &mut bare.document_mut().data_sets[0];
+    log.definitions.as_mut().unwrap().remove("NPHI");
+    assert_eq!(bare.drop_curve("NPHI").unwrap(), None);
+    assert!(!bare.headers().contains(&"NPHI".to_owned()));
+}
+
+#[test]
+fn encoding_test() {
+    let las = Las::new("./sample/latin1.las");
+    assert_eq!(las.encoding(), Encoding::Latin1);
+    let well = las.well_info();
+    assert_eq!(well.get("COMP").unwrap().value, "COMPAÑÍA PETROLERA SAÚDE");
+    assert_eq!(well.get("COMP").unwrap().description, "COMPAÑÍA");
+    assert_eq!(las.curve_params().get("DT").unwrap().unit, "µs/m");
+    assert_eq!(las.log_params().get("BHT").unwrap().unit, "°C");
+
+    // written back in the encoding it was read with
+    let bytes = std::fs::read("./sample/latin1.las").unwrap();
+    let lossless = WriteOptions {
+        lossless: true,
+        ..WriteOptions::default()
+    };
+    let mut out = vec![];
+    las.write_to(&mut out, &lossless).unwrap();
+    assert_eq!(out, bytes);
+    let mut out = vec![];
+    las.write_to(&mut out, &WriteOptions::default()).unwrap();
+    assert!(std::str::from_utf8(&out).is_err());
+    let copy = Las::from_bytes(&out).unwrap();
+    assert_eq!(copy.encoding(), Encoding::Latin1);
+    assert_eq!(copy.well_info(), well);
+
+    let utf8 = WriteOptions {
+        encoding: Some(Encoding::Utf8),
+        ..WriteOptions::default()
+    };
+    let mut out = vec![];
+    las.write_to(&mut out, &utf8).unwrap();
+    let copy: Las = String::from_utf8(out).unwrap().parse().unwrap();
+    assert_eq!(copy.curve_params(), las.curve_params());
+
+    let options = ParseOptions {
+        encoding: Some(Encoding::Utf8),
+        ..ParseOptions::default()
+    };
+    assert!(matches!(
+        Las::open_with("./sample/latin1.las", &options),
+        Err(LasError::Io(_))
+    ));
+
+    // Windows-1252 quotes and euro sign
+    let raw = std::fs::read_to_string("./sample/example.las").unwrap();
+    let raw = raw.replace(":WELL", ":WELL \u{201C}A\u{201D} \u{20AC}");
+    let bytes = raw
+        .replace('\u{201C}', "\u{93}")
+        .replace('\u{201D}', "\u{94}")
+        .replace('\u{20AC}', "\u{80}")
+        .chars()
+        .map(|c| c as u8)
+        .collect::<Vec<_>>();
+    let mut las = Las::from_bytes(&bytes).unwrap();
+    assert_eq!(las.encoding(), Encoding::Windows1252);
+    assert_eq!(las.blob, raw);
+    assert_eq!(
+        las.well_info().get("WELL").unwrap().description,
+        "WELL \u{201C}A\u{201D} \u{20AC}"
+    );
+    let mut out = vec![];
+    las.write_to(&mut out, &lossless).unwrap();
+    assert_eq!(out, bytes);
+
+    las.set_well_prop("OHM", WellProp::new("\u{3A9}", "", ""))
+        .unwrap();
+    assert!(matches!(
+        las.write_to(vec![], &lossless),
+        Err(LasError::Unencodable {
+            character: '\u{3A9}',
+            encoding: Encoding::Windows1252
+        })
+    ));
+}
+
+#[test]
+fn diagnostics_test() {
+    let raw = [
+        "~Version",
+        " VERS.  2.0 : VERSION",
+        " WRAP.  NO : ONE LINE PER DEPTH STEP",
+        "~Well",
+        " STRT.M   10.0 : START DEPTH",
+        " COMP     ACME : COMPANY",
+        " WELL.    A10",
+        "~Curve",
+        " DEPT.M   : DEPTH",
+        " GR  .GAPI : GAMMA RAY",
+        "~Tops",
+        " TOP1  1500",
+        "~A",
+        " 10.0  45.0",
+        " 10.5",
+    ]
+    .join("\n");
+    let las: Las = raw.parse().unwrap();
+    let found = las
+        .diagnostics()
+        .iter()
+        .map(|d| (d.severity, d.kind.clone(), d.line, d.column))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        vec![
+            (Severity::Error, DiagnosticKind::MissingDot, 6, 2),
+            (Severity::Warning, DiagnosticKind::MissingColon, 7, 14),
+            (
+                Severity::Warning,
+                DiagnosticKind::UnknownSection("~Tops".to_owned()),
+                11,
+                1
+            ),
+            (
+                Severity::Error,
+                DiagnosticKind::MalformedRow {
+                    expected: 2,
+                    found: 1
+                },
+                15,
+                2
+            ),
+        ]
+    );
+    assert!(las.warnings().is_empty());
+    assert_eq!(
+        las.diagnostics()[0].to_string(),
+        [
+            "error: missing dot after the mnemonic",
+            " --> line 6, column 2",
+            "  |",
+            "6 |  COMP     ACME : COMPANY",
+            "  |  ^^^^",
+        ]
+        .join("\n")
+    );
+    assert!(Las::new("./sample/example.las").diagnostics().is_empty());
+    let las = Las::new("./sample/duplicates.las");
+    assert_eq!(las.diagnostics().len(), las.warnings().len());
+
+    // malformed rows beyond the first 100 are not reported
+    let mut raw = "~V\n VERS. 2.0 :\n WRAP. NO :\n~C\n DEPT.M :\n GR.GAPI :\n~A\n".to_owned();
+    for i in 0..60_000 {
+        raw.push_str(&format!("{}.0 10.0 20.0\n", i));
+    }
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(las.row_count(), 60_000);
+    assert_eq!(las.diagnostics().len(), 100);
+    assert_eq!(las.diagnostics()[99].line, 107);
+    assert_eq!(
+        las.diagnostics()[99].to_string().lines().nth(3),
+        Some("107 | 99.0 10.0 20.0")
+    );
+    assert!(matches!(
+        las.try_data(),
+        Err(LasError::MalformedRow { line: 8, .. })
+    ));
+}
+
+#[test]
+fn validate_test() {
+    use lasrs::validate::{self, Issue, Rule};
+    assert!(validate::check(&Las::new("./sample/A10.las")).is_conforming());
+    assert!(Las::new("./sample/1046943371.las")
+        .validate()
+        .is_conforming());
+
+    // the last depth of example.las is off by 0.005
+    let report = Las::new("./sample/example.las").validate();
+    let rules = report.errors().map(|i| i.rule.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        rules[0],
+        Rule::IndexMismatch {
+            mnemonic: "STOP".to_owned(),
+            declared: 1669.75,
+            found: 1669.745
+        }
+    );
+    assert!(matches!(&rules[1], Rule::IndexMismatch { mnemonic, .. } if mnemonic == "STEP"));
+    assert_eq!(rules.len(), 2);
+
+    let raw = [
+        "~Well",
+        " STRT.M    10.0 : START DEPTH",
+        " STOP.M    10.5 : STOP DEPTH",
+        " STEP.M    0.5 : STEP",
+        " NULL.    none : NULL VALUE",
+        " COMP.    ACME : COMPANY",
+        "~Version",
+        " VERS.   2.0 : VERSION",
+        "~Parameter",
+        " BS .MM  200 : BIT SIZE",
+        "~A",
+        " 10.0",
+        " 10.5",
+        "~Other",
+        "Data above",
+    ]
+    .join("\n");
+    let las: Las = raw.parse().unwrap();
+    let report = las.validate();
+    assert!(!report.is_conforming());
+    let error = |rule| Issue {
+        severity: Severity::Error,
+        rule,
+        line: None,
+    };
+    let missing = |section: &str, mnemonic: &str| {
+        error(Rule::MissingEntry {
+            section: section.to_owned(),
+            mnemonic: mnemonic.to_owned(),
+        })
+    };
+    let mut expected = vec![
+        missing("~V", "WRAP"),
+        error(Rule::MissingSection("~C".to_owned())),
+        Issue {
+            line: Some(1),
+            ..error(Rule::SectionOrder("~V".to_owned()))
+        },
+        Issue {
+            line: Some(14),
+            ..error(Rule::SectionOrder("~A".to_owned()))
+        },
+    ];
+    for mnemonic in [
+        "WELL",
+        "FLD",
+        "LOC",
+        "PROV/CNTY/STAT/CTRY",
+        "SRVC",
+        "DATE",
+        "UWI/API",
+    ] {
+        expected.push(missing("~W", mnemonic));
+    }
+    expected.push(error(Rule::NotANumber {
+        mnemonic: "NULL".to_owned(),
+        value: "none".to_owned(),
+    }));
+    assert_eq!(report.issues, expected);
+    assert_eq!(
+        report.issues[2].to_string(),
+        "error: line 1: ~V section must be the first section"
+    );
+
+    let raw = raw
+        .replace(" 10.5\n", " 11.0\n")
+        .replace("~A", "~C\n GR.GAPI : GAMMA RAY\n~A");
+    let report: Las = raw.parse().unwrap();
+    let report = report.validate();
+    assert!(report
+        .issues
+        .contains(&error(Rule::IndexCurve("GR".to_owned()))));
+    assert!(report.issues.contains(&error(Rule::IndexMismatch {
+        mnemonic: "STEP".to_owned(),
+        declared: 0.5,
+        found: 1.0
+    })));
+
+    // sections and entries are checked as edited, whatever the blob holds
+    let mut las = Las::new("./sample/A10.las");
+    las.blob.clear();
+    assert!(las.validate().is_conforming());
+    let raw = "~V\n VERS. 2.0 : VERSION\n WRAP. NO : ONE LINE\n~C\n DEPT.M : DEPTH\n~A\n 10.0\n";
+    let mut las: Las = raw.parse().unwrap();
+    let report = las.validate();
+    assert!(report
+        .issues
+        .contains(&error(Rule::MissingSection("~W".to_owned()))));
+    las.set_well_prop("STRT", WellProp::new("M", "START DEPTH", "10.0"))
+        .unwrap();
+    let report = las.validate();
+    assert!(!report
+        .issues
+        .contains(&error(Rule::MissingSection("~W".to_owned()))));
+    assert!(report.issues.contains(&missing("~W", "STOP")));
+}
+
+#[test]
+fn house_rules_test() {
+    use lasrs::validate::{HeaderRule, HouseRules, Issue, Pattern, Rule, RuleSection, Violation};
+    let rules = HouseRules {
+        rules: vec![
+            HeaderRule {
+                non_empty: true,
+                pattern: Some(Pattern::new("^\\d{16}$").unwrap()),
+                ..HeaderRule::new(RuleSection::Well, "UWI")
+            },
+            HeaderRule {
+                allowed: vec![
+                    "ANY LOGGING COMPANY INC.".to_owned(),
+                    "ACME WIRELINE".to_owned(),
+                ],
+                ..HeaderRule::new(RuleSection::Well, "SRVC")
+            },
+            HeaderRule {
+                unit: Some("M".to_owned()),
+                ..HeaderRule::new(RuleSection::Curve, "DEPT")
+            },
+            HeaderRule::new(RuleSection::Parameter, "BS"),
+            HeaderRule::new(RuleSection::Parameter, "BHT"),
+            HeaderRule {
+                severity: Severity::Warning,
+                ..HeaderRule::new(RuleSection::Parameter, "RMF")
+            },
+        ],
+    };
+    let issue = |severity, section: &str, mnemonic: &str, violation| Issue {
+        severity,
+        rule: Rule::HouseRule {
+            section: section.to_owned(),
+            mnemonic: mnemonic.to_owned(),
+            violation,
+        },
+        line: None,
+    };
+    let report = rules.check(&Las::new("./sample/example.las"));
+    assert_eq!(
+        report.issues,
+        vec![issue(
+            Severity::Error,
+            "~W",
+            "UWI",
+            Violation::Pattern("100123401234W500".to_owned())
+        )]
+    );
+    let report = rules.check(&Las::new("./sample/A10.las"));
+    assert_eq!(
+        report.issues,
+        vec![
+            issue(
+                Severity::Error,
+                "~W",
+                "UWI",
+                Violation::Pattern("02c62c82-552d-444d-bf6b-69cd07376368".to_owned())
+            ),
+            issue(
+                Severity::Error,
+                "~W",
+                "SRVC",
+                Violation::NotAllowed(String::new())
+            ),
+            issue(
+                Severity::Error,
+                "~C",
+                "DEPT",
+                Violation::Unit("m".to_owned())
+            ),
+            issue(Severity::Error, "~P", "BS", Violation::Missing),
+            issue(Severity::Error, "~P", "BHT", Violation::Missing),
+            issue(Severity::Warning, "~P", "RMF", Violation::Missing),
+        ]
+    );
+    assert_eq!(
+        report.issues[2].to_string(),
+        "error: DEPT entry in ~C section unit `m` is not allowed"
+    );
+    let report = Las::new("./sample/A10.las").validate_with(&rules);
+    assert_eq!((report.errors().count(), report.warnings().count()), (5, 1));
+
+    #[cfg(feature = "toml")]
+    {
+        let raw = std::fs::read_to_string("./sample/rules.toml").unwrap();
+        assert_eq!(HouseRules::from_toml(&raw).unwrap(), rules);
+        let invalid = "[[rule]]\nsection = \"well\"\nmnemonic = \"UWI\"\npattern = \"(\"";
+        assert!(matches!(
+            HouseRules::from_toml(invalid),
+            Err(LasError::InvalidRules(_))
+        ));
+        assert!(matches!(
+            HouseRules::from_toml("[[rule]]\nsection = \"other\""),
+            Err(LasError::InvalidRules(_))
+        ));
+    }
+    #[cfg(feature = "json")]
+    {
+        let raw = std::fs::read_to_string("./sample/rules.json").unwrap();
+        assert_eq!(HouseRules::from_json(&raw).unwrap(), rules);
+        assert!(matches!(
+            HouseRules::from_json("{\"rule\": {}}"),
+            Err(LasError::InvalidRules(_))
+        ));
+    }
+}
+
+#[test]
+fn reader_test() {
+    // NaN values compare by their bits
+    let bits = |rows: Vec<Vec<f64>>| {
+        rows.into_iter()
+            .map(|row| row.into_iter().map(f64::to_bits).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    };
+    for path in [
+        "example.las",
+        "wrapped.las",
+        "A10.las",
+        "las3.las",
+        "latin1.las",
+    ] {
+        let path = format!("./sample/{}", path);
+        let reader = LasReader::open(&path).unwrap();
+        let las = Las::new(&path);
+        assert_eq!(reader.headers(), las.headers().as_slice());
+        assert_eq!(reader.encoding(), las.encoding());
+        assert_eq!(reader.document().well, las.document().well);
+        let rows = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(bits(rows), bits(las.data()));
+    }
+
+    let rows = LasReader::open("./sample/malformed.las")
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(rows.len(), 3);
+    assert!(matches!(
+        rows[1],
+        Err(LasError::MalformedRow {
+            line: 15,
+            expected: 3,
+            found: 2
+        })
+    ));
+    assert_eq!(rows[2].as_ref().unwrap(), &vec![1669.75, 123.45, 2550.0]);
+
+    let raw =
+        "~V\n VERS.    2.0 : VERSION\n WRAP.    NO : ONE LINE\n~W\n NULL.    -999.25 : NULL\n\
+               ~C\n DEPT.M : DEPTH\n GR.GAPI : GAMMA\n~A\n# comment\n10.0 -999.25\n\n10.5 ab\n\
+               ~O\n11.0 1.0\n";
+    let rows = LasReader::new(raw.as_bytes())
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(rows, vec![vec![10.0, -999.25], vec![10.5, -999.25]]);
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let mut reader = LasReader::with_options(raw.as_bytes(), &options).unwrap();
+    assert_eq!(reader.next().unwrap().unwrap(), vec![10.0, -999.25]);
+    assert!(matches!(
+        reader.next(),
+        Some(Err(LasError::InvalidValue {
+            line: 13,
+            column: 6,
+            ..
+        }))
+    ));
+    assert!(reader.next().is_none());
+    assert!(matches!(
+        LasReader::open("./sample/missing.las"),
+        Err(LasError::Io(_))
+    ));
+
+    // a data line that is not UTF-8 below UTF-8 headers is read as Latin-1
+    let raw = b"~V\n VERS.    2.0 : VERSION\n WRAP.    NO : ONE LINE\n\
+        ~C\n DEPT.M : DEPTH\n LITH. : LITHOLOGY\n~A\n 10.0 GR\xC8S\n 10.5 1.0\n";
+    let rows = LasReader::new(&raw[..]).unwrap().collect::<Vec<_>>();
+    let las = Las::from_bytes(raw).unwrap();
+    assert_eq!(las.encoding(), Encoding::Latin1);
+    assert_eq!(
+        bits(rows.into_iter().collect::<Result<_, _>>().unwrap()),
+        bits(las.data())
+    );
+    let mut reader = LasReader::with_options(&raw[..], &options).unwrap();
+    match reader.next() {
+        Some(Err(LasError::InvalidValue { value, .. })) => assert_eq!(value, "GR\u{C8}S"),
+        row => panic!("expected an invalid value, found {:?}", row),
+    }
+    let utf8 = ParseOptions {
+        encoding: Some(Encoding::Utf8),
+        ..Default::default()
+    };
+    let mut reader = LasReader::with_options(&raw[..], &utf8).unwrap();
+    assert!(matches!(reader.next(), Some(Err(LasError::Io(_)))));
+}
+
+#[test]
+fn large_data_test() {
+    let header =
+        "~V\n VERS.    2.0 : VERSION\n WRAP.    NO : ONE LINE\n~W\n NULL.    -999.25 : NULL\n\
+                  ~C\n DEPT.M : DEPTH\n GR.GAPI : GAMMA\n RHOB.K/M3 : DENSITY\n~A\n";
+    let value = |i: usize| format!("{:.4}", (i as f64).sin() * 100.0);
+    let lines = (0..40_000)
+        .map(|i| match i {
+            25_000 => format!("{}  {}", i, value(i)),
+            _ if i % 7 == 0 => format!("{}  -999.25  {}", i, value(i)),
+            _ => format!("{}  {}  {}", i, value(i), value(i + 1)),
+        })
+        .collect::<Vec<_>>();
+    let raw = format!("{}{}\n", header, lines.join("\n"));
+    let path = std::env::temp_dir().join(format!("lasrs-large-{}.las", std::process::id()));
+    std::fs::write(&path, &raw).unwrap();
+    let las = Las::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let data = las.data();
+    assert_eq!(data.len(), 40_000);
+    assert_eq!(
+        data[1],
+        vec![1.0, value(1).parse().unwrap(), value(2).parse().unwrap()]
+    );
+    assert_eq!(data[7][1], -999.25);
+    // the short row is padded with a missing value
+    assert_eq!(data[25_000][2], -999.25);
+    assert_eq!(data[39_999][2], value(40_000).parse::<f64>().unwrap());
+    assert!(matches!(
+        las.try_data(),
+        Err(LasError::MalformedRow {
+            line: 25_011,
+            expected: 3,
+            found: 2
+        })
+    ));
+
+    let raw = raw
+        .replacen("\n30000  ", "\n30000  abc", 1)
+        .replacen("\n35000  ", "\n35000  abc", 1);
+    let options = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        Las::from_reader_with(raw.as_bytes(), &options),
+        Err(LasError::InvalidValue {
+            line: 30_011,
+            column: 8,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn borrowed_test() {
+    let raw = std::fs::read_to_string("./sample/example.las").unwrap();
+    let log = LasRef::parse(&raw);
+    let las = Las::new("./sample/example.las");
+    assert_eq!(log.headers(), las.headers());
+    for (section, owned) in [
+        (&log.well, las.well_info()),
+        (&log.curves, las.curve_params()),
+        (&log.parameters, las.log_params()),
+    ] {
+        assert_eq!(section.len(), owned.len());
+        for prop in section {
+            assert_eq!(owned.get(prop.mnemonic), Some(&prop.to_owned()));
+        }
+    }
+    // every field is a slice of the source
+    let source = raw.as_ptr() as usize..raw.as_ptr() as usize + raw.len();
+    assert!(log
+        .well
+        .iter()
+        .all(|prop| source.contains(&(prop.value.as_ptr() as usize))));
+    assert_eq!(log.version[0].value, "2.0");
+    assert!(log.data.unwrap().starts_with("1670.000"));
+    assert!(log
+        .other
+        .unwrap()
+        .contains("Note: The logging tools became stuck"));
+    assert_eq!(log.to_owned().data(), las.data());
+
+    let raw = std::fs::read_to_string("./sample/las12.las").unwrap();
+    let log = LasRef::parse(&raw);
+    let las = Las::new("./sample/las12.las");
+    for prop in &log.well {
+        assert_eq!(las.well_info().get(prop.mnemonic), Some(&prop.to_owned()));
+    }
+
+    let raw = std::fs::read_to_string("./sample/las3.las").unwrap();
+    let log = LasRef::parse(&raw);
+    let bht = log.parameters.iter().find(|p| p.mnemonic == "BHT").unwrap();
+    assert_eq!(
+        (bht.value, bht.description, bht.format, bht.association),
+        ("35.5", "Bottom Hole Temperature", Some("F"), Some("Run[1]"))
+    );
+    assert_eq!(log.headers(), ["DEPT", "DT", "RHOB", "NMR[1]", "NMR[2]"]);
+
+    let log = LasRef::parse("~W\n COMP    ACME\n WELL.   A10\n DATE.   2020 : 1 DATE\n");
+    assert_eq!((log.well[0].mnemonic, log.well[0].value), ("COMP", "ACME"));
+    assert_eq!((log.well[1].value, log.well[1].description), ("A10", ""));
+    assert_eq!(log.well[2].description, "DATE");
+    assert!(LasRef::parse("").headers().is_empty());
+
+    // the borrowed view reads the entries of every sample file as `Las` reads them
+    for entry in std::fs::read_dir("./sample").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() != Some("las".as_ref()) {
+            continue;
+        }
+        let las = Las::new(&path);
+        let log = LasRef::parse(&las.blob);
+        for (props, section) in [
+            (&log.well, las.well_info()),
+            (&log.curves, las.curve_params()),
+            (&log.parameters, las.log_params()),
+        ] {
+            let owned = props.iter().map(WellPropRef::to_owned).collect::<Vec<_>>();
+            assert_eq!(
+                owned,
+                section.values().cloned().collect::<Vec<_>>(),
+                "{:?}",
+                path
+            );
+        }
+    }
+    let las = Las::new("./sample/A10.las");
+    let well = las.well_info();
+    let date = well.get("DATE").unwrap();
+    assert_eq!(
+        (date.value.as_str(), date.description.as_str()),
+        ("Tuesday, July 02 2002 10:57:24", "DATE")
+    );
+}
+
+#[test]
+fn header_only_test() {
+    use std::io::{BufReader, Seek, SeekFrom};
+    for path in [
+        "example.las",
+        "A10.las",
+        "las3.las",
+        "latin1.las",
+        "wrapped.las",
+    ] {
+        let path = format!("./sample/{}", path);
+        let headers = Las::open_headers(&path).unwrap();
+        let las = Las::new(&path);
+        assert_eq!(headers.headers(), las.headers());
+        assert_eq!(headers.well_info(), las.well_info());
+        assert_eq!(headers.curve_params(), las.curve_params());
+        assert_eq!(headers.log_params(), las.log_params());
+        assert_eq!(headers.encoding(), las.encoding());
+        assert_eq!(headers.row_count(), 0);
+        let offset = headers.data_offset().unwrap();
+        assert_eq!(las.data_offset(), Some(offset));
+        assert_eq!(LasReader::open(&path).unwrap().data_offset(), Some(offset));
+
+        let mut file = std::fs::File::open(&path).unwrap();
+        file.seek(SeekFrom::Start(offset)).unwrap();
+        let rows = LasReader::resume(BufReader::new(file), &headers)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let bits = |rows: &[Vec<f64>]| {
+            rows.iter()
+                .map(|row| row.iter().map(|v| v.to_bits()).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(bits(&rows), bits(&las.data()));
+    }
+
+    let raw = "~V\n VERS.    2.0 : VERSION\n~W\n STRT.M    10.0 : START\n";
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(las.data_offset(), None);
+    assert_eq!(LasReader::resume(raw.as_bytes(), &las).unwrap().count(), 0);
+    assert_eq!(LasReader::new(raw.as_bytes()).unwrap().data_offset(), None);
+
+    // a bad value in a data section preceding ~A section is an error in strict mode
+    let strict = ParseOptions {
+        strict: true,
+        ..Default::default()
+    };
+    let mut las = Las::from_reader_with(raw.as_bytes(), &strict).unwrap();
+    las.blob = "~V\n VERS.    3.0 : VERSION\n WRAP.    NO : ONE LINE\n\
+        ~Core_Definition\n TOP.M : TOP\n~Core_Data | Core_Definition\n abc\n\
+        ~Log_Definition\n DEPT.M : DEPTH\n~Log_Data | Log_Definition\n 1.0\n"
+        .to_owned();
+    assert!(matches!(
+        LasReader::resume(" 1.0\n".as_bytes(), &las),
+        Err(LasError::InvalidValue { .. })
+    ));
+
+    // the header sections alone are not written, their data would be lost
+    let mut las = Las::open_headers("./sample/example.las").unwrap();
+    las.document_mut()
+        .well
+        .as_mut()
+        .unwrap()
+        .get_mut("COMP")
+        .unwrap()
+        .value = "ACME".to_owned();
+    for lossless in [false, true] {
+        let options = WriteOptions {
+            lossless,
+            ..Default::default()
+        };
+        assert!(matches!(
+            las.write_to(vec![], &options),
+            Err(LasError::PartialDocument)
+        ));
+    }
+}
+
+#[test]
+fn selection_test() {
+    let options = ParseOptions {
+        curves: Some(vec!["ILD".to_owned(), "DEPT".to_owned(), "RHOB".to_owned()]),
+        index_range: Some((1669.9, 1669.75)),
+        ..Default::default()
+    };
+    let full = Las::new("./sample/example.las");
+    let las = Las::open_with("./sample/example.las", &options).unwrap();
+    assert_eq!(las.headers(), vec!["DEPT", "RHOB", "ILD"]);
+    assert_eq!(
+        las.curve_params().keys().collect::<Vec<_>>(),
+        vec!["DEPT", "RHOB", "ILD"]
+    );
+    let expected = full
+        .data()
+        .into_iter()
+        .filter(|row| (1669.75..=1669.9).contains(&row[0]))
+        .map(|row| vec![row[0], row[2], row[7]])
+        .collect::<Vec<_>>();
+    assert_eq!(expected.len(), 2);
+    assert_eq!(las.data(), expected);
+    assert_eq!(las.try_data().unwrap(), expected);
+
+    // a partial file is written in full layout only
+    let lossless = WriteOptions {
+        lossless: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        las.write_to(vec![], &lossless),
+        Err(LasError::PartialDocument)
+    ));
+    let mut out = vec![];
+    las.write_to(&mut out, &WriteOptions::default()).unwrap();
+    let copy = Las::from_bytes(&out).unwrap();
+    assert_eq!(copy.headers(), vec!["DEPT", "RHOB", "ILD"]);
+    assert_eq!(copy.data(), expected);
+
+    let rows = LasReader::open_with("./sample/example.las", &options).unwrap();
+    assert_eq!(rows.headers(), ["DEPT", "RHOB", "ILD"]);
+    assert_eq!(rows.collect::<Result<Vec<_>, _>>().unwrap(), expected);
+    let headers = Las::open_headers_with("./sample/example.las", &options).unwrap();
+    assert_eq!(headers.headers(), vec!["DEPT", "RHOB", "ILD"]);
+    let mut file = std::fs::File::open("./sample/example.las").unwrap();
+    std::io::Seek::seek(
+        &mut file,
+        std::io::SeekFrom::Start(headers.data_offset().unwrap()),
+    )
+    .unwrap();
+    let rows = LasReader::resume(std::io::BufReader::new(file), &headers).unwrap();
+    assert_eq!(rows.collect::<Result<Vec<_>, _>>().unwrap(), expected);
+
+    // wrapped rows are selected once joined
+    let options = ParseOptions {
+        curves: Some(vec!["DEPT".to_owned(), "DT".to_owned()]),
+        index_range: Some((910.0, 909.5)),
+        ..Default::default()
+    };
+    let las = Las::open_with("./sample/wrapped.las", &options).unwrap();
+    let rows = LasReader::open_with("./sample/wrapped.las", &options)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(las.headers(), vec!["DEPT", "DT"]);
+    assert!(!rows.is_empty());
+    assert_eq!(las.data(), rows);
+
+    // only the values kept are converted
+    let raw =
+        "~V\n VERS.    2.0 : VERSION\n WRAP.    NO : ONE LINE\n~W\n NULL.    -999.25 : NULL\n\
+               ~C\n DEPT.M : DEPTH\n GR.GAPI : GAMMA\n RHOB.K/M3 : DENSITY\n\
+               ~A\n1.0 abc 2.0\n2.0 3.0 oops\n-999.25 1.0 1.0\n";
+    let options = ParseOptions {
+        strict: true,
+        curves: Some(vec!["DEPT".to_owned(), "RHOB".to_owned()]),
+        index_range: Some((0.0, 1.5)),
+        ..Default::default()
+    };
+    let las = Las::from_reader_with(raw.as_bytes(), &options).unwrap();
+    assert_eq!(las.data(), vec![vec![1.0, 2.0]]);
+    let rows = LasReader::with_options(raw.as_bytes(), &options)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(rows, vec![vec![1.0, 2.0]]);
+}
+
+#[test]
+fn column_storage_test() {
+    let mut las = Las::new("./sample/example.las");
+    let dt = las.column("DT").unwrap();
+    assert_eq!(dt.as_ptr(), las.column("DT").unwrap().as_ptr());
+    assert_eq!(dt, las.try_column("DT").unwrap().as_slice());
+
+    let data = las.data_set("Log").unwrap().data.as_ref().unwrap();
+    assert_eq!(data.columns().len(), 8);
+    let rows = data.rows().collect::<Vec<_>>();
+    assert_eq!(rows, las.data());
+    assert_eq!(data.row(3), Some(rows[3].clone()));
+    assert_eq!(data.row(4), None);
+
+    // rows follow the columns as they are edited
+    las.drop_curve("RHOB").unwrap();
+    las.add_curve(
+        "VSH",
+        WellProp::new("V/V", "", ""),
+        vec![0.1, 0.2, 0.3, 0.4],
+    )
+    .unwrap();
+    assert_eq!(las.data()[1][1..3], [123.45, 0.45]);
+    assert_eq!(las.data()[1][7], 0.2);
+    assert_eq!(las.column("VSH"), Some(&[0.1, 0.2, 0.3, 0.4][..]));
+
+    // short rows are padded and long rows cut to one value per curve
+    let raw =
+        "~V\n VERS.    2.0 : VERSION\n WRAP.    NO : ONE LINE\n~W\n NULL.    -999.25 : NULL\n\
+               ~C\n DEPT.M : DEPTH\n GR.GAPI : GAMMA\n~A\n1.0\n2.0 3.0 4.0\n";
+    let las: Las = raw.parse().unwrap();
+    assert_eq!(las.column("GR"), Some(&[-999.25, 3.0][..]));
+    assert!(matches!(
+        las.try_data(),
+        Err(LasError::MalformedRow { line: 10, .. })
+    ));
+}
+
+#[test]
+fn curve_test() {
+    let las = Las::new("./sample/example.las");
+    let curves = las.curves();
+    assert_eq!(
+        curves.iter().map(|c| c.mnemonic).collect::<Vec<_>>(),
+        las.headers()
+    );
+    let params = las.curve_params();
+    for curve in &curves {
+        let prop = params.get(curve.mnemonic).unwrap();
+        assert_eq!(curve.unit, prop.unit);
+        assert_eq!(curve.description, prop.description);
+        assert_eq!(
+            curve.samples,
+            las.try_column(curve.mnemonic).unwrap().as_slice()
+        );
+        assert_eq!(las.curve(curve.mnemonic).as_ref(), Some(curve));
+    }
+    // samples are the columns of ~A section, not copies
+    let column = las
+        .document()
+        .log()
+        .unwrap()
+        .data
+        .as_ref()
+        .unwrap()
+        .column(3);
+    assert!(std::ptr::eq(curves[3].samples, column.unwrap()));
+    let sflu = las.curve("SFLU").unwrap();
+    assert_eq!(sflu.api_code, "07 220 04 00");
+    assert_eq!(curves[sflu.position], sflu);
+    assert_eq!(las.curve("GR"), None);
+    assert_eq!(las.index_curve(), curves.first().copied());
+
+    // curves are looked up anew once the file is changed
+    let mut las = Las::new("./sample/example.las");
+    assert_eq!(las.curve("ILD").unwrap().position, 7);
+    las.rename_curve("ILD", "RESD").unwrap();
+    las.drop_curve("DT").unwrap();
+    let prop = WellProp::new("V/V", "SHALE VOLUME", "");
+    las.add_curve("VSH", prop, vec![0.1, 0.2, 0.3, 0.4])
+        .unwrap();
+    assert_eq!(las.curve("ILD"), None);
+    assert_eq!(las.curve("RESD").unwrap().position, 6);
+    assert_eq!(las.curve("VSH").unwrap().samples, [0.1, 0.2, 0.3, 0.4]);
+    las.document_mut().data_sets.clear();
+    assert!(las.curves().is_empty());
+    assert_eq!(las.curve("RESD"), None);
+
+    // many curves are looked up by mnemonic
+    let mut raw = String::from("~V\n VERS. 2.0 :\n WRAP. NO :\n~C\n");
+    for i in 0..3000 {
+        raw.push_str(&format!(" C{}.M : CURVE {}\n", i, i));
+    }
+    let las: Las = raw.parse().unwrap();
+    for i in 0..3000 {
+        assert_eq!(las.curve(&format!("C{}", i)).unwrap().position, i);
+    }
+
+    // curves without ~A section have no samples
+    let las = Las::open_headers("./sample/example.las").unwrap();
+    assert!(las.curves().iter().all(|c| c.samples.is_empty()));
+    assert_eq!(las.index_curve().unwrap().mnemonic, "DEPT");
+}
+
+#[test]
+fn well_header_test() {
+    let las = Las::new("./sample/las12.las");
+    let header = las.well_header();
+    let step = header.step.as_ref().unwrap();
+    assert_eq!((step.value, step.unit.as_str()), (-0.125, "M"));
+    assert_eq!(header.stop.as_ref().unwrap().value, 400.0);
+    assert_eq!(header.well.as_deref(), Some("AAAAA_2"));
+    assert_eq!(header.field.as_deref(), Some("WILDCAT"));
+    assert_eq!(header.province.as_deref(), Some("ALBERTA"));
+    assert_eq!(
+        header.service_company.as_deref(),
+        Some("ANY LOGGING COMPANY INC.")
+    );
+    assert_eq!(header.date.as_deref(), Some("13-DEC-86"));
+    assert_eq!((header.state.as_ref(), header.api.as_ref()), (None, None));
+    assert!(header.extra.is_empty());
+
+    let raw = "~V\n VERS.    2.0 : VERSION\n WRAP.    NO : ONE LINE\n\
+               ~W\n STRT.FT  ABC : START\n NULL.    -999 : NULL\n API .  42-501-20130 : API\n\
+               \x20RIG .  RIG 7 : RIG NAME\n";
+    let las: Las = raw.parse().unwrap();
+    let header = las.well_header();
+    assert_eq!(header.strt, None);
+    assert_eq!(header.null, Some(-999.0));
+    assert_eq!(header.api.as_deref(), Some("42-501-20130"));
+    assert_eq!(
+        header.extra.get("RIG"),
+        Some(&WellProp::new("", "RIG NAME", "RIG 7"))
+    );
+    assert_eq!(
+        "".parse::<Las>().unwrap().well_header(),
+        WellHeader::default()
+    );
+}
+
+#[test]
+fn bom_test() {
+    let las = Las::new("./sample/bom.las");
+    let example = Las::new("./sample/example.las");
+    assert_eq!(las.version(), 2.0);
+    assert_eq!(las.headers(), example.headers());
+    assert_eq!(las.well_info(), example.well_info());
+    assert_eq!(las.data(), example.data());
+    let offset = las.data_offset().unwrap();
+    assert_eq!(Some(offset), example.data_offset().map(|o| o + 3));
+    assert_eq!(
+        Las::open_headers("./sample/bom.las").unwrap().data_offset(),
+        Some(offset)
+    );
+    let rows = LasReader::open("./sample/bom.las").unwrap();
+    assert_eq!(rows.data_offset(), Some(offset));
+    assert_eq!(rows.count(), 4);
+
+    let raw = std::fs::read_to_string("./sample/bom.las").unwrap();
+    let parsed: Las = raw.parse().unwrap();
+    assert_eq!(parsed.headers(), example.headers());
+    assert_eq!(LasRef::parse(&raw).headers(), example.headers());
+    let options = WriteOptions {
+        lossless: true,
+        ..WriteOptions::default()
+    };
+    let mut out = vec![];
+    las.write_to(&mut out, &options).unwrap();
+    assert_eq!(out, std::fs::read("./sample/bom.las").unwrap());
+
+    assert_eq!(las.encoding(), Encoding::Utf8Bom);
+    assert!(!las.blob.starts_with('\u{FEFF}'));
+    let mut out = vec![];
+    las.write_to(&mut out, &WriteOptions::default()).unwrap();
+    assert!(out.starts_with(b"\xEF\xBB\xBF~"));
+    assert_eq!(Las::from_bytes(&out).unwrap().data(), example.data());
+    let options = WriteOptions {
+        encoding: Some(Encoding::Utf8),
+        ..WriteOptions::default()
+    };
+    let mut out = vec![];
+    las.write_to(&mut out, &options).unwrap();
+    assert!(out.starts_with(b"~"));
+    let mut out = vec![];
+    las.write_data(&mut out, &WriteOptions::default()).unwrap();
+    assert!(out.starts_with(b"~A"));
+}